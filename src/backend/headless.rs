@@ -0,0 +1,159 @@
+use minifb::{Key, KeyRepeat, MouseButton};
+
+use crate::backend::Backend;
+
+/// A backend without a window. Frames are kept in memory at a fixed size and
+/// input is whatever the caller sets, so screens can be stepped frame by frame.
+pub struct HeadlessBackend {
+    width: usize,
+    height: usize,
+    open: bool,
+    frame: Vec<u32>,
+    frames: usize,
+    mouse_pos: Option<(f32, f32)>,
+    mouse_down: Vec<MouseButton>,
+    scroll_wheel: Option<(f32, f32)>,
+    keys: Vec<Key>,
+    pressed: Vec<(Key, bool)>,
+    released: Vec<Key>,
+    chars: Vec<char>,
+}
+
+impl HeadlessBackend {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            open: true,
+            frame: vec![0; width * height],
+            frames: 0,
+            mouse_pos: None,
+            mouse_down: Vec::new(),
+            scroll_wheel: None,
+            keys: Vec::new(),
+            pressed: Vec::new(),
+            released: Vec::new(),
+            chars: Vec::new(),
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn set_mouse_pos(&mut self, pos: Option<(f32, f32)>) {
+        self.mouse_pos = pos;
+    }
+
+    pub fn set_mouse_down(&mut self, button: MouseButton, down: bool) {
+        self.mouse_down.retain(|b| *b != button);
+        if down {
+            self.mouse_down.push(button);
+        }
+    }
+
+    /// Queues wheel movement for the next frame only, like a real wheel event.
+    pub fn set_scroll_wheel(&mut self, delta: Option<(f32, f32)>) {
+        self.scroll_wheel = delta;
+    }
+
+    /// Presses `key` for the next frame; pressing a held key counts as a repeat.
+    pub fn press_key(&mut self, key: Key) {
+        let repeat = self.keys.contains(&key);
+        if !repeat {
+            self.keys.push(key);
+        }
+        self.pressed.push((key, repeat));
+    }
+
+    pub fn release_key(&mut self, key: Key) {
+        if self.keys.contains(&key) {
+            self.keys.retain(|k| *k != key);
+            self.released.push(key);
+        }
+    }
+
+    /// Queues text input for the next frame.
+    pub fn type_text(&mut self, text: &str) {
+        self.chars.extend(text.chars());
+    }
+
+    /// The last presented frame, `width * height` pixels in row-major order.
+    pub fn get_frame(&self) -> &[u32] {
+        &self.frame
+    }
+
+    /// Number of frames presented so far.
+    pub fn get_frame_count(&self) -> usize {
+        self.frames
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn get_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn update_with_buffer(
+        &mut self,
+        buffer: &[u32],
+        width: usize,
+        height: usize,
+    ) -> std::io::Result<()> {
+        if buffer.len() < width * height {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "buffer is smaller than width * height",
+            ));
+        }
+        self.frame.clear();
+        self.frame.extend_from_slice(&buffer[..width * height]);
+        self.frames += 1;
+        self.scroll_wheel = None;
+        self.pressed.clear();
+        self.released.clear();
+        self.chars.clear();
+        Ok(())
+    }
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        self.mouse_pos
+    }
+
+    fn get_mouse_down(&self, button: MouseButton) -> bool {
+        self.mouse_down.contains(&button)
+    }
+
+    fn get_keys(&self) -> Vec<Key> {
+        self.keys.clone()
+    }
+
+    fn get_keys_pressed(&self, repeat: KeyRepeat) -> Vec<Key> {
+        self.pressed
+            .iter()
+            .filter(|(_, r)| !r || repeat == KeyRepeat::Yes)
+            .map(|(k, _)| *k)
+            .collect()
+    }
+
+    fn get_keys_released(&self) -> Vec<Key> {
+        self.released.clone()
+    }
+
+    fn get_chars(&self) -> Vec<char> {
+        self.chars.clone()
+    }
+
+    fn get_scroll_wheel(&self) -> Option<(f32, f32)> {
+        self.scroll_wheel
+    }
+}
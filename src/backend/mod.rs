@@ -0,0 +1,51 @@
+pub mod headless;
+pub mod window;
+
+use minifb::{Key, KeyRepeat, MouseButton};
+
+pub use headless::HeadlessBackend;
+pub use window::WindowBackend;
+
+/// The surface a [`Screen`](crate::Screen) presents its frames to and reads input from.
+pub trait Backend {
+    fn is_open(&self) -> bool;
+    fn get_size(&self) -> (usize, usize);
+    fn update_with_buffer(
+        &mut self,
+        buffer: &[u32],
+        width: usize,
+        height: usize,
+    ) -> std::io::Result<()>;
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    fn get_mouse_down(&self, _button: MouseButton) -> bool {
+        false
+    }
+
+    /// Keys currently held down.
+    fn get_keys(&self) -> Vec<Key> {
+        Vec::new()
+    }
+
+    /// Keys pressed since the last frame; with `KeyRepeat::Yes` held keys repeat.
+    fn get_keys_pressed(&self, _repeat: KeyRepeat) -> Vec<Key> {
+        Vec::new()
+    }
+
+    fn get_keys_released(&self) -> Vec<Key> {
+        Vec::new()
+    }
+
+    /// Text typed since the last frame, after keyboard layout translation.
+    fn get_chars(&self) -> Vec<char> {
+        Vec::new()
+    }
+
+    /// Wheel movement since the last frame, positive `y` meaning away from the user.
+    fn get_scroll_wheel(&self) -> Option<(f32, f32)> {
+        None
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::{Arc, Mutex};
+
+use minifb::{InputCallback, Key, KeyRepeat, MouseButton, MouseMode, Window};
+
+use crate::backend::Backend;
+
+pub struct WindowBackend {
+    pub window: Window,
+    chars: Arc<Mutex<Vec<char>>>,
+    frame_chars: Vec<char>,
+}
+
+struct CharCollector(Arc<Mutex<Vec<char>>>);
+
+impl InputCallback for CharCollector {
+    fn add_char(&mut self, uni_char: u32) {
+        if let Some(c) = char::from_u32(uni_char) {
+            self.0.lock().unwrap().push(c);
+        }
+    }
+}
+
+impl WindowBackend {
+    pub fn new(mut window: Window) -> Self {
+        let chars = Arc::new(Mutex::new(Vec::new()));
+        window.set_input_callback(Box::new(CharCollector(chars.clone())));
+        Self {
+            window,
+            chars,
+            frame_chars: Vec::new(),
+        }
+    }
+}
+
+impl Backend for WindowBackend {
+    fn is_open(&self) -> bool {
+        self.window.is_open()
+    }
+
+    fn get_size(&self) -> (usize, usize) {
+        self.window.get_size()
+    }
+
+    fn update_with_buffer(
+        &mut self,
+        buffer: &[u32],
+        width: usize,
+        height: usize,
+    ) -> std::io::Result<()> {
+        let res = self
+            .window
+            .update_with_buffer(buffer, width, height)
+            .map_err(std::io::Error::other);
+        // Events are pumped during the update, so this frame's text is complete now.
+        self.frame_chars = std::mem::take(&mut *self.chars.lock().unwrap());
+        res
+    }
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        self.window.get_mouse_pos(MouseMode::Discard)
+    }
+
+    fn get_mouse_down(&self, button: MouseButton) -> bool {
+        self.window.get_mouse_down(button)
+    }
+
+    fn get_keys(&self) -> Vec<Key> {
+        self.window.get_keys()
+    }
+
+    fn get_keys_pressed(&self, repeat: KeyRepeat) -> Vec<Key> {
+        self.window.get_keys_pressed(repeat)
+    }
+
+    fn get_keys_released(&self) -> Vec<Key> {
+        self.window.get_keys_released()
+    }
+
+    fn get_chars(&self) -> Vec<char> {
+        self.frame_chars.clone()
+    }
+
+    fn get_scroll_wheel(&self) -> Option<(f32, f32)> {
+        self.window.get_scroll_wheel()
+    }
+}
+
+impl From<Window> for WindowBackend {
+    fn from(win: Window) -> Self {
+        WindowBackend::new(win)
+    }
+}
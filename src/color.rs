@@ -0,0 +1,88 @@
+/// A color stored as `0xAARRGGBB`.
+///
+/// Plain `u32` literals convert as opaque `0xRRGGBB` colors, so `0` is black.
+/// Use [`Color::TRANSPARENT`] or an `Option<Color>` for "no fill".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color(pub u32);
+
+impl Color {
+    pub const TRANSPARENT: Color = Color(0);
+    pub const BLACK: Color = Color(0xFF000000);
+    pub const WHITE: Color = Color(0xFFFFFFFF);
+
+    pub const fn rgb(rgb: u32) -> Color {
+        Color(0xFF000000 | (rgb & 0xFFFFFF))
+    }
+
+    pub const fn argb(argb: u32) -> Color {
+        Color(argb)
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color(u32::from_be_bytes([a, r, g, b]))
+    }
+
+    pub const fn alpha(self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+
+    pub const fn red(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    pub const fn green(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    pub const fn blue(self) -> u8 {
+        self.0 as u8
+    }
+
+    pub const fn with_alpha(self, a: u8) -> Color {
+        Color((self.0 & 0xFFFFFF) | ((a as u32) << 24))
+    }
+
+    pub const fn is_transparent(self) -> bool {
+        self.alpha() == 0
+    }
+
+    pub const fn is_opaque(self) -> bool {
+        self.alpha() == 0xFF
+    }
+
+    /// Composites this color over the opaque `0xRRGGBB` pixel `dst` (source-over),
+    /// with the source alpha scaled by `coverage` in `0.0..=1.0`.
+    pub fn blend(self, dst: u32, coverage: f32) -> u32 {
+        let a = (self.alpha() as f32 * coverage.clamp(0.0, 1.0) + 0.5) as u32;
+        match a {
+            0 => dst,
+            255 => self.0 & 0xFFFFFF,
+            _ => {
+                let inv = 255 - a;
+                let mix = |s: u32, d: u32| (s * a + d * inv + 127) / 255;
+                let r = mix((self.0 >> 16) & 0xFF, (dst >> 16) & 0xFF);
+                let g = mix((self.0 >> 8) & 0xFF, (dst >> 8) & 0xFF);
+                let b = mix(self.0 & 0xFF, dst & 0xFF);
+                (r << 16) | (g << 8) | b
+            }
+        }
+    }
+}
+
+impl From<u32> for Color {
+    fn from(rgb: u32) -> Self {
+        Color::rgb(rgb)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color::rgba(r, g, b, 0xFF)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Color {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Color::rgba(r, g, b, a)
+    }
+}
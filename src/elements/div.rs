@@ -1,51 +1,214 @@
 use std::sync::Arc;
 
 use crate::{
+    elements::{
+        container_size, measure_child, render_child,
+        scroll::{render_scrolled, Scroll, ScrollbarStyle},
+    },
+    paint::Paint,
     render::RenderScope,
+    style::{Border, BoxShadow, Corners, Outline, Sides, Transform},
     widget::{Element, Widget},
 };
 
-#[derive(Default)]
+/// What happens to children that extend past the div.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    #[default]
+    Visible,
+    /// Children are clipped to the div, following its rounded corners.
+    Hidden,
+    /// Like `Hidden`, with the children scrolled by the div's `scroll` and the
+    /// same wheel and scrollbar input as a
+    /// [`ScrollView`](crate::elements::scroll::ScrollView).
+    Scroll,
+}
+
+/// How a box is drawn. `None` fields are filled in from the widget's
+/// [`Class`](crate::theme::Class)es and otherwise draw nothing.
+///
+/// The builders take plain values, e.g.
+/// `DivStyle::new().background(0x1D1D1D).corner_radius(24)`.
+#[derive(Debug, Clone, Default)]
 pub struct DivStyle {
-    pub background_color: u32,
-    pub corner_radius: usize,
+    /// A solid color or gradient; `None` leaves the area behind the div untouched.
+    pub background_color: Option<Paint>,
+    pub corner_radius: Option<Corners>,
+    /// Drawn inside the box, over the background.
+    pub border: Option<Border>,
+    /// Space between the border and the children.
+    pub padding: Option<Sides<usize>>,
+    /// Drawn outside the box without taking up space, e.g. as a focus ring.
+    pub outline: Option<Outline>,
+    /// Drawn in order, so later shadows land on top.
+    pub box_shadow: Option<Vec<BoxShadow>>,
+    /// Blurs what is behind the div before its background is drawn, with this
+    /// standard deviation in pixels; 0 turns it off.
+    pub backdrop_blur: Option<usize>,
+    pub overflow: Overflow,
+}
+
+impl DivStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn background(mut self, background: impl Into<Paint>) -> Self {
+        self.background_color = Some(background.into());
+        self
+    }
+
+    pub fn corner_radius(mut self, radius: impl Into<Corners>) -> Self {
+        self.corner_radius = Some(radius.into());
+        self
+    }
+
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    pub fn padding(mut self, padding: impl Into<Sides<usize>>) -> Self {
+        self.padding = Some(padding.into());
+        self
+    }
+
+    pub fn outline(mut self, outline: Outline) -> Self {
+        self.outline = Some(outline);
+        self
+    }
+
+    /// Adds a shadow on top of the ones already set.
+    pub fn box_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.box_shadow.get_or_insert_with(Vec::new).push(shadow);
+        self
+    }
+
+    pub fn backdrop_blur(mut self, radius: usize) -> Self {
+        self.backdrop_blur = Some(radius);
+        self
+    }
+
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Draws the shadows, outline, background and border of a `width` x `height` box.
+    pub fn draw(&self, scope: &mut RenderScope, width: usize, height: usize) {
+        let radius = self.radius();
+        let shadows = self.box_shadow.iter().flatten();
+        for shadow in shadows.clone().filter(|s| !s.inset) {
+            scope.draw_box_shadow(0, 0, width, height, radius, shadow);
+        }
+        let blur = self.backdrop_blur.unwrap_or_default();
+        scope.draw_backdrop_blur(0, 0, width, height, radius, blur);
+        if let Some(outline) = &self.outline {
+            scope.draw_outline(0, 0, width, height, radius, outline);
+        }
+        if let Some(color) = &self.background_color {
+            scope.draw_rect_rounded(0, 0, width, height, radius, color.clone());
+        }
+        for shadow in shadows.filter(|s| s.inset) {
+            scope.draw_box_shadow(0, 0, width, height, radius, shadow);
+        }
+        if let Some(border) = &self.border {
+            scope.draw_border(0, 0, width, height, radius, border);
+        }
+    }
+
+    /// The area children are laid out in, inside the border and padding,
+    /// as `(x, y, width, height)`.
+    pub fn content_box(&self, width: usize, height: usize) -> (i32, i32, usize, usize) {
+        let (b, p) = (self.border_width(), self.padding.unwrap_or_default());
+        let (left, top) = (b.left + p.left, b.top + p.top);
+        (
+            left as i32,
+            top as i32,
+            width.saturating_sub(left + b.right + p.right),
+            height.saturating_sub(top + b.bottom + p.bottom),
+        )
+    }
+
+    pub fn radius(&self) -> Corners {
+        self.corner_radius.unwrap_or_default()
+    }
+
+    pub fn border_width(&self) -> Sides<usize> {
+        self.border.map(|b| b.width).unwrap_or_default()
+    }
+
+    /// Clips what follows to the area inside the border.
+    pub fn push_clip(&self, scope: &mut RenderScope, width: usize, height: usize) {
+        let b = self.border_width();
+        scope.push_clip(
+            b.left as i32,
+            b.top as i32,
+            width.saturating_sub(b.left + b.right),
+            height.saturating_sub(b.top + b.bottom),
+            self.radius().inset(b),
+        );
+    }
 }
 
 #[derive(Default)]
 pub struct Div {
     pub children: Vec<Arc<Widget>>,
     pub style: DivStyle,
+    /// Used when `overflow` is `Scroll`.
+    pub scrollbar: ScrollbarStyle,
+    /// How far the content is scrolled, used when `overflow` is `Scroll`.
+    pub scroll: Scroll,
 }
 
 impl Element for Div {
-    fn render(&mut self, scope_parent: &mut crate::render::RenderScope) {
-        let (w, h) = scope_parent.get_size_or_parent();
-        scope_parent.draw_rect_rounded(
-            0,
-            0,
-            w,
-            h,
-            self.style.corner_radius,
-            self.style.background_color,
-        );
+    fn render(&mut self, scope_parent: &mut RenderScope) {
+        let style = scope_parent.class_style().fill_div(&self.style);
+        let (w, h) = container_size(scope_parent, &style, |cw, ch| {
+            self.content_size(scope_parent, cw, ch)
+        });
+        scope_parent.reserve(w, h);
+        style.draw(scope_parent, w, h);
 
-        let mut scope = RenderScope::new(w, h);
+        if style.overflow == Overflow::Scroll {
+            render_scrolled(
+                scope_parent,
+                &self.children,
+                &style,
+                &self.scrollbar,
+                &self.scroll,
+                (w, h),
+            );
+            return;
+        }
 
-        for elem in &self.children {
-            scope.clear();
-            if let Some(t) = elem.get() {
-                scope.set_transform(&t);
-            }
+        let clip = style.overflow != Overflow::Visible;
+        if clip {
+            style.push_clip(scope_parent, w, h);
+        }
 
-            elem.0.lock().unwrap().render(&mut scope);
+        let (cx, cy, cw, ch) = style.content_box(w, h);
+        for elem in &self.children {
+            let t = elem.get::<Transform>().unwrap_or_default();
+            let mut scope = render_child(scope_parent, elem, &t, cw, ch);
+            scope.translate(cx, cy);
+            scope_parent.merge(scope);
+        }
 
-            if let Some(t) = elem.get() {
-                scope.set_transform(&t);
-            }
-            scope_parent.merge(scope.clone());
+        if clip {
+            scope_parent.pop_clip();
         }
     }
 
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        let style = scope.class_style().fill_div(&self.style);
+        container_size(scope, &style, |cw, ch| self.content_size(scope, cw, ch))
+    }
+
+    fn children(&self) -> Vec<Arc<Widget>> {
+        self.children.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -56,6 +219,19 @@ impl Element for Div {
 }
 
 impl Div {
+    /// The box around the children at their own positions, margins included.
+    fn content_size(&self, scope: &RenderScope, width: usize, height: usize) -> (usize, usize) {
+        self.children.iter().fold((0, 0), |(w, h), elem| {
+            let t = elem.get::<Transform>().unwrap_or_default();
+            let (cw, ch) = measure_child(scope, elem, &t, width, height);
+            let m = t.margin;
+            (
+                w.max(t.x.offset().max(0) as usize + cw + m.left + m.right),
+                h.max(t.y.offset().max(0) as usize + ch + m.top + m.bottom),
+            )
+        })
+    }
+
     pub fn draw<E: Element + 'static>(&mut self, element: E) -> &Arc<Widget> {
         self.children.push(Arc::new(Widget::new(Box::new(element))));
         self.children.last().unwrap()
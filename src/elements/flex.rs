@@ -0,0 +1,325 @@
+use std::sync::Arc;
+
+use crate::{
+    component,
+    elements::{
+        container_size,
+        div::{DivStyle, Overflow},
+        measure_child, render_child,
+    },
+    render::RenderScope,
+    style::{Position, Sides, Transform},
+    widget::{Element, Widget},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlexDirection {
+    #[default]
+    Row,
+    Column,
+}
+
+/// Distribution of children along the main axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// Placement of children along the cross axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Children with an `Auto` cross dimension fill the container.
+    Stretch,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FlexLayout {
+    pub direction: FlexDirection,
+    pub gap: usize,
+    pub justify: Justify,
+    pub align: Align,
+}
+
+component!(FlexItem {
+    pub grow: f32,
+    pub shrink: f32,
+    pub align: Option<Align>,
+});
+
+/// A [`Div`](crate::elements::div::Div) that positions its children in a row or column.
+///
+/// Children are measured with their own [`Transform`] dimensions, then placed
+/// along the main axis inside the style's padding; their `x`/`y` positions are
+/// ignored but margins keep space around them. An `Auto` width or height
+/// fits the children.
+#[derive(Default)]
+pub struct Flex {
+    pub children: Vec<Arc<Widget>>,
+    pub style: DivStyle,
+    pub layout: FlexLayout,
+}
+
+impl Element for Flex {
+    fn render(&mut self, scope_parent: &mut RenderScope) {
+        let style = scope_parent.class_style().fill_div(&self.style);
+        let (w, h) = container_size(scope_parent, &style, |cw, ch| {
+            self.content_size(scope_parent, cw, ch)
+        });
+        scope_parent.reserve(w, h);
+        style.draw(scope_parent, w, h);
+
+        let clip = style.overflow != Overflow::Visible;
+        if clip {
+            style.push_clip(scope_parent, w, h);
+        }
+
+        let (inner_x, inner_y, inner_w, inner_h) = style.content_box(w, h);
+        let row = self.layout.direction == FlexDirection::Row;
+        let (main_size, cross_size) = if row {
+            (inner_w, inner_h)
+        } else {
+            (inner_h, inner_w)
+        };
+
+        let items: Vec<(Transform, FlexItem, usize, usize)> = self
+            .children
+            .iter()
+            .map(|elem| {
+                let t = elem.get::<Transform>().unwrap_or_default();
+                let item = elem.get::<FlexItem>().unwrap_or_default();
+                let (cw, ch) = measure_child(scope_parent, elem, &t, inner_w, inner_h);
+                let (main, cross) = if row { (cw, ch) } else { (ch, cw) };
+                (t, item, main, cross)
+            })
+            .collect();
+
+        let mains = self.resolve_main(&items, main_size);
+        let margins: usize = items.iter().map(|i| self.margins(&i.0).0).sum();
+        let used =
+            mains.iter().sum::<usize>() + margins + self.layout.gap * items.len().saturating_sub(1);
+        let free = main_size.saturating_sub(used);
+        let (mut cursor, spacing) = justify(self.layout.justify, free, items.len());
+        let spacing = spacing + self.layout.gap;
+
+        for (elem, ((t, item, _, cross), main)) in
+            self.children.iter().zip(items.into_iter().zip(mains))
+        {
+            let align = item.align.unwrap_or(self.layout.align);
+            let (main_margin, cross_margin) = self.margins(&t);
+            let cross_space = cross_size.saturating_sub(cross_margin);
+            let cross_auto = if row { &t.height } else { &t.width }.is_auto();
+            let cross = if align == Align::Stretch && cross_auto {
+                cross_space
+            } else {
+                cross.min(cross_space)
+            };
+            let cross_pos = match align {
+                Align::Start | Align::Stretch => 0,
+                Align::Center => (cross_space - cross) / 2,
+                Align::End => cross_space - cross,
+            };
+
+            // Positions are of the margin box; the transform adds the leading margins.
+            let (x, y, cw, ch) = if row {
+                (cursor, cross_pos, main, cross)
+            } else {
+                (cross_pos, cursor, cross, main)
+            };
+
+            let mut placed = t.clone().dimensions(cw, ch);
+            placed.x = Position::Const(x as i32 + inner_x);
+            placed.y = Position::Const(y as i32 + inner_y);
+            scope_parent.merge(render_child(scope_parent, elem, &placed, w, h));
+
+            cursor += main + main_margin + spacing;
+        }
+
+        if clip {
+            scope_parent.pop_clip();
+        }
+    }
+
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        let style = scope.class_style().fill_div(&self.style);
+        container_size(scope, &style, |cw, ch| self.content_size(scope, cw, ch))
+    }
+
+    fn children(&self) -> Vec<Arc<Widget>> {
+        self.children.clone()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Flex {
+    pub fn row() -> Self {
+        Self::default()
+    }
+
+    pub fn column() -> Self {
+        Self {
+            layout: FlexLayout {
+                direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.layout.gap = gap;
+        self
+    }
+
+    pub fn padding(mut self, padding: impl Into<Sides<usize>>) -> Self {
+        self.style.padding = Some(padding.into());
+        self
+    }
+
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.layout.justify = justify;
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.layout.align = align;
+        self
+    }
+
+    pub fn draw<E: Element + 'static>(&mut self, element: E) -> &Arc<Widget> {
+        self.children.push(Arc::new(Widget::new(Box::new(element))));
+        self.children.last().unwrap()
+    }
+
+    /// The children back to back along the main axis with the gaps between
+    /// them, as `(width, height)`.
+    fn content_size(&self, scope: &RenderScope, width: usize, height: usize) -> (usize, usize) {
+        let row = self.layout.direction == FlexDirection::Row;
+        let (main, cross) = self.children.iter().fold((0, 0), |(main, cross), elem| {
+            let t = elem.get::<Transform>().unwrap_or_default();
+            let (cw, ch) = measure_child(scope, elem, &t, width, height);
+            let (main_margin, cross_margin) = self.margins(&t);
+            let (child_main, child_cross) = if row { (cw, ch) } else { (ch, cw) };
+            (
+                main + child_main + main_margin,
+                cross.max(child_cross + cross_margin),
+            )
+        });
+        let main = main + self.layout.gap * self.children.len().saturating_sub(1);
+        if row {
+            (main, cross)
+        } else {
+            (cross, main)
+        }
+    }
+
+    /// The total margins of a child along the main and cross axes.
+    fn margins(&self, t: &Transform) -> (usize, usize) {
+        let m = t.margin;
+        let (horizontal, vertical) = (m.left + m.right, m.top + m.bottom);
+        if self.layout.direction == FlexDirection::Row {
+            (horizontal, vertical)
+        } else {
+            (vertical, horizontal)
+        }
+    }
+
+    /// Grows or shrinks the measured main sizes so they fill `main_size`.
+    fn resolve_main(
+        &self,
+        items: &[(Transform, FlexItem, usize, usize)],
+        main_size: usize,
+    ) -> Vec<usize> {
+        let gaps = self.layout.gap * items.len().saturating_sub(1);
+        let margins: usize = items.iter().map(|i| self.margins(&i.0).0).sum();
+        let basis: usize = items.iter().map(|i| i.2).sum::<usize>() + gaps + margins;
+
+        if basis < main_size {
+            let total: f32 = items.iter().map(|i| i.1.grow.max(0.0)).sum();
+            if total > 0.0 {
+                let free = (main_size - basis) as f32;
+                return items
+                    .iter()
+                    .map(|i| i.2 + (free * i.1.grow.max(0.0) / total) as usize)
+                    .collect();
+            }
+        } else if basis > main_size {
+            let total: f32 = items.iter().map(|i| i.1.shrink.max(0.0) * i.2 as f32).sum();
+            if total > 0.0 {
+                let over = (basis - main_size) as f32;
+                return items
+                    .iter()
+                    .map(|i| {
+                        let cut = over * i.1.shrink.max(0.0) * i.2 as f32 / total;
+                        i.2.saturating_sub(cut.ceil() as usize)
+                    })
+                    .collect();
+            }
+        }
+
+        items.iter().map(|i| i.2).collect()
+    }
+}
+
+impl FlexItem {
+    pub fn new() -> Self {
+        Self {
+            grow: 0.0,
+            shrink: 1.0,
+            align: None,
+        }
+    }
+
+    pub fn grow(grow: f32) -> Self {
+        Self {
+            grow,
+            ..Self::new()
+        }
+    }
+
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the leading offset and the extra space between items.
+fn justify(justify: Justify, free: usize, count: usize) -> (usize, usize) {
+    match justify {
+        Justify::Start => (0, 0),
+        Justify::Center => (free / 2, 0),
+        Justify::End => (free, 0),
+        Justify::SpaceBetween if count > 1 => (0, free / (count - 1)),
+        Justify::SpaceBetween => (0, 0),
+        Justify::SpaceAround if count > 0 => (free / count / 2, free / count),
+        Justify::SpaceEvenly => (free / (count + 1), free / (count + 1)),
+        Justify::SpaceAround => (0, 0),
+    }
+}
@@ -0,0 +1,409 @@
+use std::sync::Arc;
+
+use crate::{
+    component,
+    elements::{
+        container_size,
+        div::{DivStyle, Overflow},
+        measure_child, render_child,
+    },
+    render::RenderScope,
+    style::{Sides, Transform},
+    widget::{Element, Widget},
+};
+
+/// Size of one grid row or column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    Fixed(usize),
+    /// A share of the space left after fixed and auto tracks.
+    Fraction(f32),
+    /// As large as the largest child placed in this track; children spanning
+    /// several tracks share their size between the auto ones.
+    Auto,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GridLayout {
+    pub columns: Vec<Track>,
+    /// Rows beyond these are added as `Auto` tracks.
+    pub rows: Vec<Track>,
+    pub column_gap: usize,
+    pub row_gap: usize,
+}
+
+component!(GridItem {
+    pub row: Option<usize>,
+    pub column: Option<usize>,
+    pub row_span: usize,
+    pub column_span: usize,
+});
+
+/// A [`Div`](crate::elements::div::Div) that places its children in cells.
+///
+/// Each child is rendered with its cell as the parent size, so its own
+/// [`Transform`] positions it inside the cell, clear of its margins. Children without a [`GridItem`]
+/// fill the next free cells in row order, and ones with only a row or column
+/// take the first free cell in it. An `Auto` width or height fits the tracks.
+#[derive(Default)]
+pub struct Grid {
+    pub children: Vec<Arc<Widget>>,
+    pub style: DivStyle,
+    pub layout: GridLayout,
+}
+
+struct Placement {
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+}
+
+impl Element for Grid {
+    fn render(&mut self, scope_parent: &mut RenderScope) {
+        let style = scope_parent.class_style().fill_div(&self.style);
+        let (w, h) = container_size(scope_parent, &style, |cw, ch| {
+            self.content_size(scope_parent, cw, ch)
+        });
+        scope_parent.reserve(w, h);
+        style.draw(scope_parent, w, h);
+
+        let clip = style.overflow != Overflow::Visible;
+        if clip {
+            style.push_clip(scope_parent, w, h);
+        }
+
+        let (inner_x, inner_y, inner_w, inner_h) = style.content_box(w, h);
+        let placements = self.place();
+        let (columns, rows) = self.tracks(scope_parent, &placements, inner_w, inner_h);
+
+        for (elem, p) in self.children.iter().zip(&placements) {
+            let (x, cw) = span(&columns, self.layout.column_gap, p.column, p.column_span);
+            let (y, ch) = span(&rows, self.layout.row_gap, p.row, p.row_span);
+
+            let t = elem.get::<Transform>().unwrap_or_default();
+            let mut scope = render_child(scope_parent, elem, &t, cw, ch);
+            scope.translate(x as i32 + inner_x, y as i32 + inner_y);
+            scope_parent.merge(scope);
+        }
+
+        if clip {
+            scope_parent.pop_clip();
+        }
+    }
+
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        let style = scope.class_style().fill_div(&self.style);
+        container_size(scope, &style, |cw, ch| self.content_size(scope, cw, ch))
+    }
+
+    fn children(&self) -> Vec<Arc<Widget>> {
+        self.children.clone()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Grid {
+    pub fn new(columns: Vec<Track>, rows: Vec<Track>) -> Self {
+        Self {
+            layout: GridLayout {
+                columns,
+                rows,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    pub fn gap(mut self, column_gap: usize, row_gap: usize) -> Self {
+        self.layout.column_gap = column_gap;
+        self.layout.row_gap = row_gap;
+        self
+    }
+
+    pub fn padding(mut self, padding: impl Into<Sides<usize>>) -> Self {
+        self.style.padding = Some(padding.into());
+        self
+    }
+
+    pub fn draw<E: Element + 'static>(&mut self, element: E) -> &Arc<Widget> {
+        self.children.push(Arc::new(Widget::new(Box::new(element))));
+        self.children.last().unwrap()
+    }
+
+    /// The tracks and gaps between them, as `(width, height)`. `Fraction`
+    /// tracks still share the space the parent leaves.
+    fn content_size(&self, scope: &RenderScope, width: usize, height: usize) -> (usize, usize) {
+        let (columns, rows) = self.tracks(scope, &self.place(), width, height);
+        (
+            span(&columns, self.layout.column_gap, 0, columns.len()).1,
+            span(&rows, self.layout.row_gap, 0, rows.len()).1,
+        )
+    }
+
+    /// Assigns every child a cell.
+    fn place(&self) -> Vec<Placement> {
+        let columns = self.layout.columns.len().max(1);
+        let mut taken: Vec<Vec<bool>> = Vec::new();
+        let mut cursor = 0;
+
+        self.children
+            .iter()
+            .map(|elem| {
+                let item = elem.get::<GridItem>().unwrap_or_default();
+                let column_span = item.column_span.clamp(1, columns);
+                let row_span = item.row_span.max(1);
+
+                // Cells of the span at `(r, c)` that are already taken.
+                let overlap = |r: usize, c: usize| {
+                    (0..row_span)
+                        .flat_map(|dr| (0..column_span).map(move |dc| (r + dr, c + dc)))
+                        .filter(|&(r, c)| is_taken(&taken, r, c))
+                        .count()
+                };
+                let last_column = columns - column_span;
+                let (row, column) = match (item.row, item.column) {
+                    (Some(r), Some(c)) => (r, c.min(last_column)),
+                    // A full row still keeps the item, over as few others as possible.
+                    (Some(r), None) => (r, (0..=last_column).min_by_key(|&c| overlap(r, c)).unwrap()),
+                    (None, Some(c)) => {
+                        let c = c.min(last_column);
+                        ((0..).find(|&r| overlap(r, c) == 0).unwrap(), c)
+                    }
+                    (None, None) => loop {
+                        let (r, c) = (cursor / columns, cursor % columns);
+                        cursor += 1;
+                        if c <= last_column && overlap(r, c) == 0 {
+                            break (r, c);
+                        }
+                    },
+                };
+
+                for dr in 0..row_span {
+                    if taken.len() <= row + dr {
+                        taken.resize(row + dr + 1, vec![false; columns]);
+                    }
+                    for dc in 0..column_span {
+                        taken[row + dr][column + dc] = true;
+                    }
+                }
+
+                Placement {
+                    row,
+                    column,
+                    row_span,
+                    column_span,
+                }
+            })
+            .collect()
+    }
+
+    /// Resolves the column and row sizes, measuring children only as far as
+    /// `Auto` tracks need them. Heights are measured at the resolved column
+    /// widths, so wrapping content gets the width of its cell.
+    fn tracks(
+        &self,
+        scope: &RenderScope,
+        placements: &[Placement],
+        inner_w: usize,
+        inner_h: usize,
+    ) -> (Vec<usize>, Vec<usize>) {
+        let (column_gap, row_gap) = (self.layout.column_gap, self.layout.row_gap);
+        let columns = if self.layout.columns.is_empty() {
+            vec![Track::Fraction(1.0)]
+        } else {
+            self.layout.columns.clone()
+        };
+        let mut rows = self.layout.rows.clone();
+        let row_count = placements
+            .iter()
+            .map(|p| p.row + p.row_span)
+            .max()
+            .unwrap_or(0);
+        if rows.len() < row_count {
+            rows.resize(row_count, Track::Auto);
+        }
+
+        let fixed_rows = fixed_sizes(&rows);
+        let measure = |elem: &Arc<Widget>, p: &Placement, width: usize| {
+            let t = elem.get::<Transform>().unwrap_or_default();
+            let height = fixed_span(&fixed_rows, row_gap, p.row, p.row_span).unwrap_or(inner_h);
+            let (w, h) = measure_child(scope, elem, &t, width, height);
+            let m = t.margin;
+            (w + m.left + m.right, h + m.top + m.bottom)
+        };
+
+        let widths: Vec<usize> = if columns.contains(&Track::Auto) {
+            let fixed_columns = fixed_sizes(&columns);
+            self.children
+                .iter()
+                .zip(placements)
+                .map(|(elem, p)| {
+                    let width = fixed_span(&fixed_columns, column_gap, p.column, p.column_span);
+                    measure(elem, p, width.unwrap_or(inner_w)).0
+                })
+                .collect()
+        } else {
+            vec![0; placements.len()]
+        };
+        let columns = resolve_tracks(
+            &columns,
+            inner_w,
+            column_gap,
+            placements
+                .iter()
+                .zip(&widths)
+                .map(|(p, &w)| (p.column, p.column_span, w)),
+        );
+
+        let heights: Vec<usize> = self
+            .children
+            .iter()
+            .zip(placements)
+            .map(|(elem, p)| {
+                let (_, width) = span(&columns, column_gap, p.column, p.column_span);
+                measure(elem, p, width).1
+            })
+            .collect();
+        let rows = resolve_tracks(
+            &rows,
+            inner_h,
+            row_gap,
+            placements
+                .iter()
+                .zip(&heights)
+                .map(|(p, &h)| (p.row, p.row_span, h)),
+        );
+
+        (columns, rows)
+    }
+}
+
+impl GridItem {
+    pub fn new() -> Self {
+        Self {
+            row: None,
+            column: None,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    pub fn at(row: usize, column: usize) -> Self {
+        Self {
+            row: Some(row),
+            column: Some(column),
+            ..Self::new()
+        }
+    }
+
+    pub fn span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.row_span = row_span;
+        self.column_span = column_span;
+        self
+    }
+}
+
+impl Default for GridItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_taken(taken: &[Vec<bool>], row: usize, column: usize) -> bool {
+    taken
+        .get(row)
+        .and_then(|r| r.get(column))
+        .copied()
+        .unwrap_or(false)
+}
+
+/// Resolves track sizes from `(start, span, size)` of the placed children.
+fn resolve_tracks(
+    tracks: &[Track],
+    available: usize,
+    gap: usize,
+    items: impl Iterator<Item = (usize, usize, usize)>,
+) -> Vec<usize> {
+    let mut sizes: Vec<usize> = fixed_sizes(tracks)
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect();
+
+    // Single track items first, then spanning ones grow the auto tracks they
+    // cover by an equal share of what is still missing.
+    let mut items: Vec<(usize, usize, usize)> = items.collect();
+    items.sort_by_key(|&(_, span, _)| span);
+    for (start, span, size) in items {
+        let end = (start + span).min(tracks.len());
+        let auto: Vec<usize> = (start..end)
+            .filter(|&i| tracks[i] == Track::Auto)
+            .collect();
+        if auto.is_empty() {
+            continue;
+        }
+        let covered = sizes[start..end].iter().sum::<usize>() + gap * (end - start - 1);
+        let missing = size.saturating_sub(covered);
+        for (n, &i) in auto.iter().enumerate() {
+            // The first tracks take the remainder, so the shares add up exactly.
+            let extra = usize::from(n < missing % auto.len());
+            sizes[i] += missing / auto.len() + extra;
+        }
+    }
+
+    let used = sizes.iter().sum::<usize>() + gap * tracks.len().saturating_sub(1);
+    let free = available.saturating_sub(used) as f32;
+    let total: f32 = tracks
+        .iter()
+        .map(|t| match t {
+            Track::Fraction(f) => f.max(0.0),
+            _ => 0.0,
+        })
+        .sum();
+
+    if total > 0.0 {
+        for (size, track) in sizes.iter_mut().zip(tracks) {
+            if let Track::Fraction(f) = track {
+                *size = (free * f.max(0.0) / total) as usize;
+            }
+        }
+    }
+
+    sizes
+}
+
+/// The size of every `Fixed` track.
+fn fixed_sizes(tracks: &[Track]) -> Vec<Option<usize>> {
+    tracks
+        .iter()
+        .map(|t| match t {
+            Track::Fixed(n) => Some(*n),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The length of `span` tracks starting at `start`, if they are all fixed.
+fn fixed_span(sizes: &[Option<usize>], gap: usize, start: usize, span: usize) -> Option<usize> {
+    let tracks = sizes.get(start..start + span)?;
+    let total = tracks.iter().copied().sum::<Option<usize>>()?;
+    Some(total + gap * (span - 1))
+}
+
+/// Returns the offset and length covered by `span` tracks starting at `start`.
+fn span(sizes: &[usize], gap: usize, start: usize, span: usize) -> (usize, usize) {
+    let offset = sizes[..start.min(sizes.len())]
+        .iter()
+        .map(|s| s + gap)
+        .sum();
+    let end = (start + span).min(sizes.len());
+    let length = sizes[start.min(end)..end].iter().sum::<usize>() + gap * span.saturating_sub(1);
+    (offset, length)
+}
@@ -0,0 +1,117 @@
+use std::{io, path::Path, sync::Arc};
+
+use crate::{
+    image::{self, Filter, ImageData},
+    render::RenderScope,
+    widget::Element,
+};
+
+/// How an image is sized inside its element.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Fit {
+    /// As large as possible while showing the whole image.
+    #[default]
+    Contain,
+    /// As small as possible while covering the whole element; the rest is cut off.
+    Cover,
+    /// Stretched to the element, ignoring the aspect ratio.
+    Fill,
+    /// At its own size, centered.
+    None,
+}
+
+/// Shows a decoded image.
+///
+/// Without a size from its [`Transform`](crate::style::Transform) the element
+/// takes the image's own size; with only one dimension set, the other
+/// follows the image's aspect ratio.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub data: Arc<ImageData>,
+    pub fit: Fit,
+    pub filter: Filter,
+}
+
+impl Element for Image {
+    fn render(&mut self, scope: &mut RenderScope) {
+        let (iw, ih) = (self.data.width, self.data.height);
+        if iw == 0 || ih == 0 {
+            return;
+        }
+
+        let (w, h) = match scope.get_size() {
+            (0, 0) => (iw, ih),
+            (0, h) => (h * iw / ih, h),
+            (w, 0) => (w, w * ih / iw),
+            size => size,
+        };
+        scope.reserve(w, h);
+
+        let (dw, dh) = match self.fit {
+            Fit::Fill => (w, h),
+            Fit::None => (iw, ih),
+            Fit::Contain | Fit::Cover => {
+                let sx = w as f32 / iw as f32;
+                let sy = h as f32 / ih as f32;
+                let s = if self.fit == Fit::Contain {
+                    sx.min(sy)
+                } else {
+                    sx.max(sy)
+                };
+                (
+                    (iw as f32 * s).round() as usize,
+                    (ih as f32 * s).round() as usize,
+                )
+            }
+        };
+        let x = (w as i32 - dw as i32).div_euclid(2);
+        let y = (h as i32 - dh as i32).div_euclid(2);
+
+        let overflow = dw > w || dh > h;
+        if overflow {
+            scope.push_clip(0, 0, w, h, 0);
+        }
+        scope.draw_image(x, y, dw, dh, &self.data, self.filter);
+        if overflow {
+            scope.pop_clip();
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Image {
+    pub fn new(data: Arc<ImageData>) -> Self {
+        Self {
+            data,
+            fit: Fit::default(),
+            filter: Filter::default(),
+        }
+    }
+
+    /// Loads a PNG or BMP through the shared image cache.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        image::load(path).map(Self::new)
+    }
+
+    /// Decodes a PNG or BMP through the shared image cache.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        image::from_bytes(bytes).map(Self::new)
+    }
+
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
@@ -0,0 +1,406 @@
+use std::sync::{Arc, Mutex};
+
+use minifb::Key;
+use once_cell::sync::Lazy;
+use rusttype::Scale;
+
+use crate::{
+    color::Color,
+    component,
+    elements::div::DivStyle,
+    events::{CharInput, Event, FocusIn, FocusOut, KeyDown, KeyUp, Modifiers},
+    render::{font_for, RenderScope},
+    style::{Inherit, Sides, TextStyle},
+    utils,
+    widget::{Element, Widget},
+};
+
+/// Copy/paste buffer shared by every text input in the app.
+pub(crate) static CLIPBOARD: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new(String::new()));
+
+component!(OnChange(pub fn(&Arc<Widget>, &str)));
+
+const CARET_WIDTH: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct TextInputStyle {
+    pub size: f32,
+    pub padding: usize,
+    pub color: Color,
+    pub placeholder_color: Color,
+    pub caret_color: Color,
+    pub selection_color: Color,
+}
+
+/// A single-line editable text field.
+///
+/// Takes keyboard focus through [`KeyboardExtension`](crate::extensions::keyboard::KeyboardExtension)
+/// and calls the widget's [`OnChange`] with the new value after every edit.
+/// The text size and color are inherited like [`Text`](crate::elements::text::Text)'s,
+/// so direct writes to them in `style` only show once `inherit` is cleared.
+#[derive(Default)]
+pub struct TextInput {
+    pub value: String,
+    pub placeholder: String,
+    pub style: TextInputStyle,
+    pub inherit: Inherit,
+    pub background: DivStyle,
+    /// Byte offset of the caret in `value`.
+    caret: usize,
+    /// Byte offset of the other end of the selection, if any.
+    anchor: Option<usize>,
+    focused: bool,
+    modifiers: Modifiers,
+    scroll_x: f32,
+}
+
+impl Element for TextInput {
+    fn render(&mut self, scope: &mut RenderScope) {
+        self.clamp_caret();
+        let text_style = self.style.resolve(scope, self.inherit);
+        let line_h = line_height(&text_style);
+        let pad = self.style.padding;
+
+        let (w, h) = self.measure(scope);
+        scope.reserve(w, h);
+        let background = scope.class_style().fill_div(&self.background);
+        background.draw(scope, w, h);
+
+        // Room is left for the caret after the last char, so it isn't clipped.
+        let inner_w = w.saturating_sub(pad * 2 + CARET_WIDTH) as f32;
+        let carets = utils::styled_caret_positions(&self.value, &text_style);
+        let caret_x = carets[self.char_index(self.caret)];
+
+        // Keep the caret inside the visible part of the field.
+        if caret_x - self.scroll_x > inner_w {
+            self.scroll_x = caret_x - inner_w;
+        } else if caret_x < self.scroll_x {
+            self.scroll_x = caret_x;
+        }
+        self.scroll_x = self
+            .scroll_x
+            .min((carets.last().copied().unwrap_or(0.0) - inner_w).max(0.0));
+
+        let x0 = pad as i32 - self.scroll_x.round() as i32;
+        let y0 = (h.saturating_sub(line_h) / 2) as i32;
+        scope.push_clip(
+            pad as i32,
+            0,
+            w.saturating_sub(pad * 2),
+            h,
+            background.radius().inset(Sides::symmetric(0, pad)),
+        );
+
+        if let Some((start, end)) = self.selection() {
+            let sx = carets[self.char_index(start)];
+            let ex = carets[self.char_index(end)];
+            scope.draw_rect(
+                x0 + sx.round() as i32,
+                y0,
+                (ex - sx).round() as usize,
+                line_h,
+                self.style.selection_color,
+            );
+        }
+
+        if self.value.is_empty() {
+            let placeholder = TextStyle {
+                color: self.style.placeholder_color.into(),
+                ..text_style
+            };
+            scope.draw_styled_text(x0, y0, &self.placeholder, &placeholder);
+        } else {
+            scope.draw_styled_text(x0, y0, &self.value, &text_style);
+        }
+
+        if self.focused {
+            scope.draw_rect(
+                x0 + caret_x.round() as i32,
+                y0,
+                CARET_WIDTH,
+                line_h,
+                self.style.caret_color,
+            );
+        }
+        scope.pop_clip();
+    }
+
+    /// As wide as the transform or parent, one line high unless set.
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        let line_h = line_height(&self.style.resolve(scope, self.inherit));
+        let (w, set_h) = (scope.get_size_or_parent().0, scope.get_size().1);
+        let h = if set_h > 0 {
+            set_h
+        } else {
+            line_h + self.style.padding * 2
+        };
+        (w, h)
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn event(&mut self, widget: &Arc<Widget>, event: &dyn Event) {
+        self.clamp_caret();
+        let changed = if event.as_any().is::<FocusIn>() {
+            self.focused = true;
+            false
+        } else if event.as_any().is::<FocusOut>() {
+            self.focused = false;
+            self.anchor = None;
+            false
+        } else if let Some(e) = event.as_any().downcast_ref::<KeyUp>() {
+            self.modifiers = e.modifiers;
+            false
+        } else if let Some(CharInput(c)) = event.as_any().downcast_ref::<CharInput>() {
+            // Shortcuts like Ctrl+V may also produce a char on some platforms.
+            if self.modifiers.command() && !self.modifiers.alt {
+                false
+            } else {
+                self.insert(&c.to_string());
+                true
+            }
+        } else if let Some(e) = event.as_any().downcast_ref::<KeyDown>() {
+            self.modifiers = e.modifiers;
+            self.key_down(e)
+        } else {
+            false
+        };
+
+        if changed {
+            if let Some(on_change) = widget.get::<OnChange>() {
+                (on_change.0)(widget, &self.value);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl TextInput {
+    pub fn new(placeholder: &str) -> Self {
+        Self {
+            placeholder: placeholder.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets the whole style, so nothing is inherited.
+    pub fn style(mut self, style: TextInputStyle) -> Self {
+        self.style = style;
+        self.inherit = Inherit::NONE;
+        self
+    }
+
+    /// Replaces the text without firing [`OnChange`] and moves the caret to the end.
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.caret = self.value.len();
+        self.anchor = None;
+    }
+
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// The selected byte range, if it is not empty.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.anchor?;
+        if anchor == self.caret {
+            None
+        } else {
+            Some((anchor.min(self.caret), anchor.max(self.caret)))
+        }
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.selection()
+            .map(|(s, e)| &self.value[s..e])
+            .unwrap_or("")
+    }
+
+    /// Replaces the selection, or inserts at the caret, with `text`.
+    pub fn insert(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        self.delete_selection();
+        self.value.insert_str(self.caret, &text);
+        self.caret += text.len();
+    }
+
+    /// Returns whether the value changed.
+    fn key_down(&mut self, e: &KeyDown) -> bool {
+        let m = e.modifiers;
+        let word = m.ctrl || m.alt;
+
+        match e.key {
+            Key::Left | Key::Right | Key::Home | Key::End => {
+                let target = match (e.key, self.selection()) {
+                    // Collapse an existing selection to the side being moved towards.
+                    (Key::Left, Some((start, _))) if !m.shift => start,
+                    (Key::Right, Some((_, end))) if !m.shift => end,
+                    (Key::Left, _) if word => prev_word(&self.value, self.caret),
+                    (Key::Right, _) if word => next_word(&self.value, self.caret),
+                    (Key::Left, _) => prev_char(&self.value, self.caret),
+                    (Key::Right, _) => next_char(&self.value, self.caret),
+                    (Key::Home, _) => 0,
+                    _ => self.value.len(),
+                };
+                self.move_caret(target, m.shift);
+                false
+            }
+            Key::Backspace => {
+                if self.selection().is_none() {
+                    let start = if word {
+                        prev_word(&self.value, self.caret)
+                    } else {
+                        prev_char(&self.value, self.caret)
+                    };
+                    self.anchor = Some(start);
+                }
+                self.delete_selection()
+            }
+            Key::Delete => {
+                if self.selection().is_none() {
+                    let end = if word {
+                        next_word(&self.value, self.caret)
+                    } else {
+                        next_char(&self.value, self.caret)
+                    };
+                    self.anchor = Some(end);
+                }
+                self.delete_selection()
+            }
+            Key::A if m.command() => {
+                self.anchor = Some(0);
+                self.caret = self.value.len();
+                false
+            }
+            Key::C if m.command() => {
+                if self.selection().is_some() {
+                    *CLIPBOARD.lock().unwrap() = self.selected_text().to_string();
+                }
+                false
+            }
+            Key::X if m.command() => {
+                if self.selection().is_some() {
+                    *CLIPBOARD.lock().unwrap() = self.selected_text().to_string();
+                }
+                self.delete_selection()
+            }
+            Key::V if m.command() => {
+                let text = CLIPBOARD.lock().unwrap().clone();
+                self.insert(&text);
+                !text.is_empty()
+            }
+            _ => false,
+        }
+    }
+
+    fn move_caret(&mut self, target: usize, extend: bool) {
+        if extend {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
+        }
+        self.caret = target;
+    }
+
+    /// Returns whether anything was deleted.
+    fn delete_selection(&mut self) -> bool {
+        let deleted = match self.selection() {
+            Some((start, end)) => {
+                self.value.replace_range(start..end, "");
+                self.caret = start;
+                true
+            }
+            None => false,
+        };
+        self.anchor = None;
+        deleted
+    }
+
+    /// Keeps the caret and anchor valid after `value` was changed directly.
+    fn clamp_caret(&mut self) {
+        let valid = |i: usize| i <= self.value.len() && self.value.is_char_boundary(i);
+        if !valid(self.caret) {
+            self.caret = self.value.len();
+        }
+        if self.anchor.is_some_and(|a| !valid(a)) {
+            self.anchor = None;
+        }
+    }
+
+    fn char_index(&self, byte: usize) -> usize {
+        self.value[..byte].chars().count()
+    }
+}
+
+impl TextInputStyle {
+    /// The style the value is drawn with, taking what `inherit` selects from the scope.
+    pub(crate) fn resolve(&self, scope: &RenderScope, inherit: Inherit) -> TextStyle {
+        let mut text = TextStyle::new(self.size, self.color);
+        text.inherit(scope.text_style(), inherit);
+        text
+    }
+}
+
+impl Default for TextInputStyle {
+    fn default() -> Self {
+        Self {
+            size: 24.0,
+            padding: 8,
+            color: Color::WHITE,
+            placeholder_color: Color::rgb(0x808080),
+            caret_color: Color::WHITE,
+            selection_color: Color::argb(0x803D7BFD),
+        }
+    }
+}
+
+/// Height of one line of text in `style`, in whole pixels.
+pub(crate) fn line_height(style: &TextStyle) -> usize {
+    let v_metrics = font_for(style).v_metrics(Scale::uniform(style.size));
+    (v_metrics.ascent - v_metrics.descent).ceil() as usize
+}
+
+pub(crate) fn prev_char(s: &str, i: usize) -> usize {
+    s[..i].char_indices().last().map(|(i, _)| i).unwrap_or(0)
+}
+
+pub(crate) fn next_char(s: &str, i: usize) -> usize {
+    s[i..].chars().next().map(|c| i + c.len_utf8()).unwrap_or(i)
+}
+
+/// Start of the word before `i`, skipping whitespace first.
+pub(crate) fn prev_word(s: &str, i: usize) -> usize {
+    let trimmed = s[..i].trim_end();
+    trimmed
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map(|(j, c)| j + c.len_utf8())
+        .unwrap_or(0)
+}
+
+/// End of the word after `i`, skipping whitespace first.
+pub(crate) fn next_word(s: &str, i: usize) -> usize {
+    let rest = &s[i..];
+    let start = rest.len() - rest.trim_start().len();
+    rest[start..]
+        .char_indices()
+        .find(|(_, c)| c.is_whitespace())
+        .map(|(j, _)| i + start + j)
+        .unwrap_or(s.len())
+}
@@ -1,13 +1,32 @@
 pub mod div;
-
-use crate::widget::Element;
-
-pub struct Rect(pub u32, pub usize);
+pub mod flex;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod richtext;
+pub mod scroll;
+pub mod text;
+pub mod textarea;
+
+use std::sync::Arc;
+
+use crate::{
+    elements::{div::DivStyle, text::Text},
+    paint::Paint,
+    render::RenderScope,
+    style::Transform,
+    widget::{Element, Widget},
+};
+
+/// Font size of the `String` shorthand, scaled by the `f32` of `(String, f32)`.
+const SHORTHAND_SIZE: f32 = 40.0;
+
+pub struct Rect(pub Paint, pub usize);
 
 impl Element for Rect {
     fn render(&mut self, scope: &mut crate::render::RenderScope) {
         let (w, h) = scope.get_size();
-        scope.draw_rect_rounded(0, 0, w, h, self.1, self.0);
+        scope.draw_rect_rounded(0, 0, w, h, self.1, self.0.clone());
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -19,9 +38,10 @@ impl Element for Rect {
     }
 }
 
+/// Shorthand for a 40px [`Text`].
 impl Element for String {
     fn render(&mut self, scope: &mut crate::render::RenderScope) {
-        scope.draw_text(0, 0, 40.0, self, 0xffffff);
+        Text::new(self).size(SHORTHAND_SIZE).render(scope);
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -33,9 +53,12 @@ impl Element for String {
     }
 }
 
+/// Shorthand for a [`Text`] at 40px times the given scale.
 impl Element for (String, f32) {
     fn render(&mut self, scope: &mut crate::render::RenderScope) {
-        scope.draw_text(0, 0, 40.0 * self.1, &self.0, 0xffffff);
+        Text::new(&self.0)
+            .size(SHORTHAND_SIZE * self.1)
+            .render(scope);
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -80,3 +103,70 @@ pub fn custom<F: FnMut(&mut crate::render::RenderScope) + Send + Sync + 'static>
 ) -> Box<dyn FnMut(&mut crate::render::RenderScope) + Send + Sync> {
     Box::new(f)
 }
+
+/// Renders `widget` into a fresh scope of the given parent size, placed by
+/// `transform` and styled as a child of `parent`.
+pub(crate) fn render_child(
+    parent: &RenderScope,
+    widget: &Arc<Widget>,
+    transform: &Transform,
+    parent_width: usize,
+    parent_height: usize,
+) -> RenderScope {
+    let mut scope = RenderScope::child(parent_width, parent_height);
+    scope.set_widget(widget);
+    scope.inherit_theme(widget, parent);
+    scope.set_transform(transform);
+    widget.0.lock().unwrap().render(&mut scope);
+    scope.set_transform(transform);
+    scope.update_size();
+    scope
+}
+
+/// The size [`render_child`] would give `widget`, from [`Element::measure`].
+pub(crate) fn measure_child(
+    parent: &RenderScope,
+    widget: &Arc<Widget>,
+    transform: &Transform,
+    parent_width: usize,
+    parent_height: usize,
+) -> (usize, usize) {
+    let mut scope = RenderScope::child(parent_width, parent_height);
+    scope.inherit_theme(widget, parent);
+    scope.set_transform(transform);
+    let (w, h) = widget.0.lock().unwrap().measure(&mut scope);
+    scope.set_transform(transform);
+    scope.update_size();
+    let (sw, sh) = scope.get_max_size();
+    (w.max(sw), h.max(sh))
+}
+
+/// The size of a container drawn with `style` in `scope`. Dimensions set by
+/// its transform are kept, `Auto` ones fit `content`: the size the children
+/// need when given the space inside the border and padding.
+pub(crate) fn container_size(
+    scope: &RenderScope,
+    style: &DivStyle,
+    content: impl FnOnce(usize, usize) -> (usize, usize),
+) -> (usize, usize) {
+    let (set_w, set_h) = scope.get_size();
+    let (w, h) = scope.get_size_or_parent();
+    if set_w > 0 && set_h > 0 {
+        return (w, h);
+    }
+    let (_, _, inner_w, inner_h) = style.content_box(w, h);
+    let (content_w, content_h) = content(inner_w, inner_h);
+    let (b, p) = (style.border_width(), style.padding.unwrap_or_default());
+    (
+        if set_w > 0 {
+            set_w
+        } else {
+            content_w + b.left + b.right + p.left + p.right
+        },
+        if set_h > 0 {
+            set_h
+        } else {
+            content_h + b.top + b.bottom + p.top + p.bottom
+        },
+    )
+}
@@ -0,0 +1,393 @@
+use std::sync::Arc;
+
+use rusttype::Scale;
+
+use crate::{
+    component,
+    elements::text::TextAlign,
+    events::{Click, Event},
+    paint::Paint,
+    render::{font_for, RenderScope},
+    style::{FontWeight, Inherit, TextStyle},
+    utils,
+    widget::{Element, Widget},
+};
+
+component!(OnLinkClick(pub fn(&Arc<Widget>, &str)));
+
+/// A piece of a [`RichText`] paragraph drawn in one style.
+#[derive(Debug, Clone, Default)]
+pub struct Span {
+    pub text: String,
+    pub style: TextStyle,
+    /// Style properties replaced by the [`RichText`]'s parent on every
+    /// render; the builder methods clear the ones they set.
+    pub inherit: Inherit,
+    /// Passed to [`OnLinkClick`] when the span is clicked.
+    pub link: Option<String>,
+}
+
+/// A paragraph of differently styled [`Span`]s, wrapped as one block.
+///
+/// Spans on a line share a baseline, so larger text pushes the line down
+/// instead of overlapping. Clicks delivered by
+/// [`MouseExtension`](crate::extensions::mouse::MouseExtension) on a span
+/// with a link call the widget's [`OnLinkClick`]; other code can hit-test
+/// with [`RichText::span_at`].
+#[derive(Debug, Clone)]
+pub struct RichText {
+    pub spans: Vec<Span>,
+    /// `Justify` is treated as `Left`.
+    pub align: TextAlign,
+    /// Multiple of the tallest span's natural line height.
+    pub line_height: f32,
+    pub max_width: Option<usize>,
+    /// Where each fragment was drawn on the last render, for hit testing.
+    boxes: Vec<HitBox>,
+}
+
+#[derive(Debug, Clone)]
+struct HitBox {
+    span: usize,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+/// Consecutive text from one span on one line.
+struct Fragment {
+    span: usize,
+    text: String,
+    x: f32,
+    width: f32,
+    ascent: f32,
+}
+
+#[derive(Default)]
+struct Line {
+    fragments: Vec<Fragment>,
+    /// Up to the end of the last word, ignoring trailing spaces.
+    width: f32,
+    ascent: f32,
+    descent: f32,
+    /// Started by wrapping rather than by `\n`, so leading spaces are dropped.
+    wrapped: bool,
+}
+
+impl Element for RichText {
+    fn render(&mut self, scope: &mut RenderScope) {
+        let (lines, width) = self.lines(scope);
+
+        self.boxes.clear();
+        let mut y = 0.0;
+        for line in &lines {
+            let natural = line.ascent - line.descent;
+            let height = natural * self.line_height.max(0.0);
+            let baseline = y + (height - natural) / 2.0 + line.ascent;
+            let free = (width as f32 - line.width).max(0.0);
+            let offset = match self.align {
+                TextAlign::Left | TextAlign::Justify => 0.0,
+                TextAlign::Center => free / 2.0,
+                TextAlign::Right => free,
+            };
+
+            for fragment in &line.fragments {
+                let x = offset + fragment.x;
+                scope.draw_styled_text(
+                    x.round() as i32,
+                    (baseline - fragment.ascent).round() as i32,
+                    &fragment.text,
+                    &self.spans[fragment.span].style,
+                );
+                self.boxes.push(HitBox {
+                    span: fragment.span,
+                    x,
+                    y,
+                    width: fragment.width,
+                    height,
+                });
+            }
+            y += height;
+        }
+
+        scope.reserve(width, y.ceil() as usize);
+    }
+
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        let (lines, width) = self.lines(scope);
+        let height: f32 = lines
+            .iter()
+            .map(|l| (l.ascent - l.descent) * self.line_height.max(0.0))
+            .sum();
+        (width, height.ceil() as usize)
+    }
+
+    fn event(&mut self, widget: &Arc<Widget>, event: &dyn Event) {
+        if let Some(click) = event.as_any().downcast_ref::<Click>() {
+            if let Some(link) = self.link_at(click.x, click.y) {
+                if let Some(on_link_click) = widget.get::<OnLinkClick>() {
+                    (on_link_click.0)(widget, link);
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl RichText {
+    pub fn new(spans: Vec<Span>) -> Self {
+        Self {
+            spans,
+            align: TextAlign::Left,
+            line_height: 1.0,
+            max_width: None,
+            boxes: Vec::new(),
+        }
+    }
+
+    pub fn push(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Index of the span drawn under a point relative to the element, as of the last render.
+    pub fn span_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.boxes
+            .iter()
+            .find(|b| x >= b.x && y >= b.y && x < b.x + b.width && y < b.y + b.height)
+            .map(|b| b.span)
+    }
+
+    pub fn link_at(&self, x: f32, y: f32) -> Option<&str> {
+        self.span_at(x, y)
+            .and_then(|i| self.spans[i].link.as_deref())
+    }
+
+    /// The lines for the width `scope` allows and the width of the block.
+    fn lines(&mut self, scope: &RenderScope) -> (Vec<Line>, usize) {
+        for span in &mut self.spans {
+            span.style.inherit(scope.text_style(), span.inherit);
+        }
+        let set_width = scope.get_size().0;
+        let max_width = if set_width > 0 {
+            Some(set_width)
+        } else {
+            self.max_width
+        };
+        let lines = self.layout(max_width.map(|w| w as f32).unwrap_or(0.0));
+
+        let width = max_width.unwrap_or_else(|| {
+            lines
+                .iter()
+                .map(|l| l.width.ceil() as usize)
+                .max()
+                .unwrap_or(0)
+        });
+        (lines, width)
+    }
+
+    /// Breaks the spans into lines for a wrap `width`, 0 meaning unlimited.
+    fn layout(&self, width: f32) -> Vec<Line> {
+        let mut lines = vec![Line::default()];
+        let mut x = 0.0;
+
+        for (index, span) in self.spans.iter().enumerate() {
+            let v_metrics = font_for(&span.style).v_metrics(Scale::uniform(span.style.size));
+            let (ascent, descent) = (v_metrics.ascent, v_metrics.descent);
+
+            for (p, paragraph) in span.text.split('\n').enumerate() {
+                if p > 0 {
+                    lines.push(Line::default());
+                    x = 0.0;
+                }
+                let line = lines.last_mut().unwrap();
+                line.ascent = line.ascent.max(ascent);
+                line.descent = line.descent.min(descent);
+
+                for token in tokens(paragraph) {
+                    let space = token.starts_with(char::is_whitespace);
+                    let carets = utils::styled_caret_positions(token, &span.style);
+                    let token_w = carets[carets.len() - 1];
+
+                    if !space && width > 0.0 && x > 0.0 && x + token_w > width {
+                        lines.push(Line {
+                            wrapped: true,
+                            ..Default::default()
+                        });
+                        x = 0.0;
+                    }
+                    if space && x == 0.0 && lines.last().unwrap().wrapped {
+                        continue;
+                    }
+
+                    // Words wider than a whole line are split between chars.
+                    let rows = if space {
+                        vec![0]
+                    } else {
+                        utils::wrap_rows(token, &carets, width, false)
+                    };
+                    let bytes: Vec<usize> = token
+                        .char_indices()
+                        .map(|(i, _)| i)
+                        .chain(std::iter::once(token.len()))
+                        .collect();
+
+                    for (r, &start) in rows.iter().enumerate() {
+                        if r > 0 {
+                            lines.push(Line {
+                                wrapped: true,
+                                ..Default::default()
+                            });
+                            x = 0.0;
+                        }
+                        let end = rows.get(r + 1).copied().unwrap_or(carets.len() - 1);
+                        let piece_w = carets[end] - carets[start];
+
+                        let line = lines.last_mut().unwrap();
+                        line.ascent = line.ascent.max(ascent);
+                        line.descent = line.descent.min(descent);
+                        line.push(index, &token[bytes[start]..bytes[end]], x, piece_w, ascent);
+                        if !space {
+                            line.width = x + piece_w;
+                        }
+                        x += piece_w;
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
+
+impl Default for RichText {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl Line {
+    /// Adds text, joining it to the previous fragment if that is the same span.
+    fn push(&mut self, span: usize, text: &str, x: f32, width: f32, ascent: f32) {
+        if let Some(last) = self.fragments.last_mut() {
+            if last.span == span {
+                last.text.push_str(text);
+                last.width = x + width - last.x;
+                return;
+            }
+        }
+        self.fragments.push(Fragment {
+            span,
+            text: text.to_string(),
+            x,
+            width,
+            ascent,
+        });
+    }
+}
+
+impl Span {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the whole style, so nothing is inherited.
+    pub fn style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self.inherit = Inherit::NONE;
+        self
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.style.size = size;
+        self.inherit.size = false;
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Paint>) -> Self {
+        self.style.color = color.into();
+        self.inherit.color = false;
+        self
+    }
+
+    pub fn family(mut self, family: &str) -> Self {
+        self.style.family = Some(family.to_string());
+        self.inherit.family = false;
+        self
+    }
+
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.style.weight = weight;
+        self.inherit.weight = false;
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.style.italic = italic;
+        self.inherit.italic = false;
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.style.underline = underline;
+        self.inherit.underline = false;
+        self
+    }
+
+    pub fn link(mut self, link: &str) -> Self {
+        self.link = Some(link.to_string());
+        self
+    }
+}
+
+impl From<&str> for Span {
+    fn from(text: &str) -> Self {
+        Span::new(text)
+    }
+}
+
+/// Splits `text` into alternating runs of whitespace and non-whitespace.
+fn tokens(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let space = rest.starts_with(char::is_whitespace);
+        let end = rest
+            .find(|c: char| c.is_whitespace() != space)
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return None;
+        }
+        let (token, tail) = rest.split_at(end);
+        rest = tail;
+        Some(token)
+    })
+}
@@ -0,0 +1,267 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    color::Color,
+    component,
+    elements::{div::DivStyle, render_child},
+    render::RenderScope,
+    style::Transform,
+    widget::{Element, Widget},
+};
+
+/// Velocity kept per frame while coasting with inertia.
+const FRICTION: f32 = 0.92;
+
+#[derive(Debug, Clone, Default)]
+pub struct ScrollState {
+    pub offset: (f32, f32),
+    /// Pixels per frame, only used when `inertia` is enabled.
+    pub velocity: (f32, f32),
+    pub inertia: bool,
+    /// Size of the children, updated on every render.
+    pub content: (usize, usize),
+    /// Size of the visible area, updated on every render.
+    pub viewport: (usize, usize),
+    /// Also sets the minimum thumb length, three times the width.
+    pub scrollbar_width: usize,
+    /// Distance from the top of the scrollbar thumb to the cursor while dragging it.
+    pub drag: Option<f32>,
+}
+
+component!(Scroll(pub Arc<Mutex<ScrollState>>));
+
+#[derive(Debug, Clone)]
+pub struct ScrollbarStyle {
+    pub width: usize,
+    pub track_color: Option<Color>,
+    pub thumb_color: Color,
+}
+
+/// A clipped [`Div`](crate::elements::div::Div) whose children can be scrolled.
+///
+/// Input comes from [`ScrollExtension`](crate::extensions::scroll::ScrollExtension);
+/// the offset is shared through the [`Scroll`] component, so other code can
+/// read or change it with `widget.get::<Scroll>()`.
+#[derive(Default)]
+pub struct ScrollView {
+    pub children: Vec<Arc<Widget>>,
+    pub style: DivStyle,
+    pub scrollbar: ScrollbarStyle,
+    pub scroll: Scroll,
+}
+
+impl Element for ScrollView {
+    fn render(&mut self, scope_parent: &mut RenderScope) {
+        let (w, h) = scope_parent.get_size_or_parent();
+        let style = scope_parent.class_style().fill_div(&self.style);
+        style.draw(scope_parent, w, h);
+
+        render_scrolled(
+            scope_parent,
+            &self.children,
+            &style,
+            &self.scrollbar,
+            &self.scroll,
+            (w, h),
+        );
+    }
+
+    /// The content scrolls inside the box, so only its own size counts.
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        scope.get_size_or_parent()
+    }
+
+    fn children(&self) -> Vec<Arc<Widget>> {
+        self.children.clone()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Draws `children` into the `w`×`h` box drawn with `style`, clipped to it and
+/// moved by `scroll`, with a scrollbar while they overflow. Shared by
+/// [`ScrollView`] and [`Div`](crate::elements::div::Div)s with
+/// [`Overflow::Scroll`](crate::elements::div::Overflow::Scroll).
+pub(crate) fn render_scrolled(
+    scope_parent: &mut RenderScope,
+    children: &[Arc<Widget>],
+    style: &DivStyle,
+    scrollbar: &ScrollbarStyle,
+    scroll: &Scroll,
+    (w, h): (usize, usize),
+) {
+    let (inner_x, inner_y, inner_w, inner_h) = style.content_box(w, h);
+    let scopes: Vec<(RenderScope, Transform)> = children
+        .iter()
+        .map(|elem| {
+            let t = elem.get::<Transform>().unwrap_or_default();
+            (render_child(scope_parent, elem, &t, inner_w, inner_h), t)
+        })
+        .collect();
+
+    let ends = scopes.iter().fold((0, 0), |(cw, ch), (scope, t)| {
+        let raw = scope.get_transform();
+        let (sw, sh) = scope.get_max_size();
+        (
+            cw.max((raw.x + (sw + t.margin.right) as i32).max(0) as usize),
+            ch.max((raw.y + (sh + t.margin.bottom) as i32).max(0) as usize),
+        )
+    });
+    // The border and padding scroll with the content, so they count on both ends.
+    let content = (
+        ends.0 + w.saturating_sub(inner_w),
+        ends.1 + h.saturating_sub(inner_h),
+    );
+
+    let (ox, oy) = {
+        let mut state = scroll.0.lock().unwrap();
+        state.content = content;
+        state.viewport = (w, h);
+        state.scrollbar_width = scrollbar.width;
+        state.step();
+        state.offset
+    };
+
+    style.push_clip(scope_parent, w, h);
+    for (mut scope, _) in scopes {
+        scope.translate(inner_x - ox.round() as i32, inner_y - oy.round() as i32);
+        scope_parent.merge(scope);
+    }
+
+    if let Some((y, len)) = scroll.thumb() {
+        let x = w.saturating_sub(scrollbar.width) as i32;
+        let radius = scrollbar.width / 2;
+        if let Some(color) = scrollbar.track_color {
+            scope_parent.draw_rect(x, 0, scrollbar.width, h, color);
+        }
+        scope_parent.draw_rect_rounded(x, y, scrollbar.width, len, radius, scrollbar.thumb_color);
+    }
+    scope_parent.pop_clip();
+}
+
+impl ScrollView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn inertia(self, inertia: bool) -> Self {
+        self.scroll.0.lock().unwrap().inertia = inertia;
+        self
+    }
+
+    pub fn draw<E: Element + 'static>(&mut self, element: E) -> &Arc<Widget> {
+        self.children.push(Arc::new(Widget::new(Box::new(element))));
+        self.children.last().unwrap()
+    }
+}
+
+impl Default for ScrollbarStyle {
+    fn default() -> Self {
+        Self {
+            width: 8,
+            track_color: None,
+            thumb_color: Color::argb(0x80FFFFFF),
+        }
+    }
+}
+
+impl Scroll {
+    pub fn offset(&self) -> (f32, f32) {
+        self.0.lock().unwrap().offset
+    }
+
+    /// Jumps to an offset; it is clamped to the content on the next render.
+    pub fn scroll_to(&self, x: f32, y: f32) {
+        let mut state = self.0.lock().unwrap();
+        state.offset = (x, y);
+        state.velocity = (0.0, 0.0);
+    }
+
+    /// Scrolls by a wheel-like delta, coasting afterwards if inertia is enabled.
+    pub fn scroll_by(&self, dx: f32, dy: f32) {
+        let mut state = self.0.lock().unwrap();
+        if state.inertia {
+            state.velocity.0 += dx * (1.0 - FRICTION);
+            state.velocity.1 += dy * (1.0 - FRICTION);
+        } else {
+            state.offset.0 += dx;
+            state.offset.1 += dy;
+            state.clamp();
+        }
+    }
+
+    /// Position and length of the vertical scrollbar thumb, if the content overflows.
+    pub fn thumb(&self) -> Option<(i32, usize)> {
+        self.0.lock().unwrap().thumb()
+    }
+}
+
+impl Default for Scroll {
+    fn default() -> Self {
+        Scroll(Arc::new(Mutex::new(ScrollState {
+            scrollbar_width: ScrollbarStyle::default().width,
+            ..Default::default()
+        })))
+    }
+}
+
+impl ScrollState {
+    pub fn max_offset(&self) -> (f32, f32) {
+        (
+            self.content.0.saturating_sub(self.viewport.0) as f32,
+            self.content.1.saturating_sub(self.viewport.1) as f32,
+        )
+    }
+
+    pub fn clamp(&mut self) {
+        let (mx, my) = self.max_offset();
+        self.offset.0 = self.offset.0.clamp(0.0, mx);
+        self.offset.1 = self.offset.1.clamp(0.0, my);
+    }
+
+    /// Advances inertial scrolling by one frame.
+    pub fn step(&mut self) {
+        if self.inertia && self.drag.is_none() {
+            self.offset.0 += self.velocity.0;
+            self.offset.1 += self.velocity.1;
+            self.velocity.0 *= FRICTION;
+            self.velocity.1 *= FRICTION;
+            if self.velocity.0.abs() < 0.1 && self.velocity.1.abs() < 0.1 {
+                self.velocity = (0.0, 0.0);
+            }
+        }
+        self.clamp();
+    }
+
+    pub fn thumb(&self) -> Option<(i32, usize)> {
+        let (content, viewport) = (self.content.1, self.viewport.1);
+        if content <= viewport || viewport == 0 {
+            return None;
+        }
+
+        let len = (viewport * viewport / content)
+            .max(self.scrollbar_width * 3)
+            .min(viewport);
+        let travel = (viewport - len) as f32;
+        let pos = travel * self.offset.1 / self.max_offset().1;
+        Some((pos.round() as i32, len))
+    }
+
+    /// Moves the offset so the thumb's top edge sits at `thumb_y` within the track.
+    pub fn drag_thumb_to(&mut self, thumb_y: f32) {
+        if let Some((_, len)) = self.thumb() {
+            let travel = self.viewport.1.saturating_sub(len) as f32;
+            if travel > 0.0 {
+                self.offset.1 = thumb_y.clamp(0.0, travel) / travel * self.max_offset().1;
+                self.velocity = (0.0, 0.0);
+                self.clamp();
+            }
+        }
+    }
+}
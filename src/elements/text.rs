@@ -0,0 +1,346 @@
+use rusttype::Scale;
+
+use crate::{
+    paint::Paint,
+    render::{font_for, RenderScope},
+    style::{FontWeight, Inherit, TextStyle},
+    utils,
+    widget::Element,
+};
+
+const ELLIPSIS: &str = "…";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextWrap {
+    /// Break between words, splitting only words wider than a line.
+    #[default]
+    Word,
+    /// Break at whichever char reaches the edge.
+    Char,
+    /// Only break at `\n`.
+    None,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretches the spaces of wrapped lines to fill the width; last lines stay left aligned.
+    Justify,
+}
+
+/// A block of text that wraps to a maximum width.
+///
+/// The width to wrap at comes from the [`Transform`](crate::style::Transform)
+/// if it sets one, otherwise from `max_width`. Without either, lines only
+/// break at `\n`. The element reports the size of the laid out block, so it
+/// can be centered and stacked like any other element.
+#[derive(Debug, Clone)]
+pub struct Text {
+    pub text: String,
+    /// Direct writes to a property `inherit` selects are overwritten on the
+    /// next render; use the builders, or clear the flag as well.
+    pub style: TextStyle,
+    /// Style properties replaced by the parent's on every render; the
+    /// builder methods clear the ones they set.
+    pub inherit: Inherit,
+    pub align: TextAlign,
+    pub wrap: TextWrap,
+    /// Multiple of the font's natural line height.
+    pub line_height: f32,
+    pub max_width: Option<usize>,
+    pub max_lines: Option<usize>,
+    /// End lines cut off by `max_lines` or too wide to fit with "…".
+    pub ellipsis: bool,
+}
+
+struct Line {
+    text: String,
+    width: f32,
+    /// Whether `Justify` should stretch this line.
+    wrapped: bool,
+}
+
+impl Element for Text {
+    fn render(&mut self, scope: &mut RenderScope) {
+        self.style.inherit(scope.text_style(), self.inherit);
+        let v_metrics = font_for(&self.style).v_metrics(Scale::uniform(self.style.size));
+        let glyph_h = v_metrics.ascent - v_metrics.descent;
+        let line_h = glyph_h * self.line_height.max(0.0);
+
+        let set_width = scope.get_size().0;
+        let max_width = if set_width > 0 {
+            Some(set_width)
+        } else {
+            self.max_width
+        };
+        let lines = self.layout(max_width.map(|w| w as f32).unwrap_or(0.0));
+
+        let widest = lines
+            .iter()
+            .map(|l| l.width.ceil() as usize)
+            .max()
+            .unwrap_or(0);
+        // A set width is kept, `max_width` only caps the widest line.
+        let width = if set_width > 0 {
+            set_width
+        } else {
+            self.max_width.map_or(widest, |max| widest.min(max))
+        };
+        let height = (lines.len() as f32 * line_h).ceil() as usize;
+        scope.reserve(width, height);
+
+        for (i, line) in lines.iter().enumerate() {
+            let y = (i as f32 * line_h + (line_h - glyph_h) / 2.0).round() as i32;
+            let free = (width as f32 - line.width).max(0.0);
+            let x = match self.align {
+                TextAlign::Left | TextAlign::Justify => 0.0,
+                TextAlign::Center => free / 2.0,
+                TextAlign::Right => free,
+            };
+
+            if self.align == TextAlign::Justify && line.wrapped {
+                self.draw_justified(scope, &line.text, y, free);
+            } else {
+                scope.draw_styled_text(x.round() as i32, y, &line.text, &self.style);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl Text {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the whole style, so nothing is inherited.
+    pub fn style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self.inherit = Inherit::NONE;
+        self
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.style.size = size;
+        self.inherit.size = false;
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Paint>) -> Self {
+        self.style.color = color.into();
+        self.inherit.color = false;
+        self
+    }
+
+    pub fn family(mut self, family: &str) -> Self {
+        self.style.family = Some(family.to_string());
+        self.inherit.family = false;
+        self
+    }
+
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.style.weight = weight;
+        self.inherit.weight = false;
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.style.italic = italic;
+        self.inherit.italic = false;
+        self
+    }
+
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.style.letter_spacing = letter_spacing;
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.style.underline = underline;
+        self.inherit.underline = false;
+        self
+    }
+
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.style.strikethrough = strikethrough;
+        self.inherit.strikethrough = false;
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: TextWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    pub fn ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Splits the text into the lines drawn for a wrap `width`, 0 meaning unlimited.
+    fn layout(&self, width: f32) -> Vec<Line> {
+        let wrap_width = if self.wrap == TextWrap::None {
+            0.0
+        } else {
+            width
+        };
+
+        let mut lines = Vec::new();
+        for paragraph in self.text.split('\n') {
+            let carets = utils::styled_caret_positions(paragraph, &self.style);
+            let bytes: Vec<usize> = paragraph
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain(std::iter::once(paragraph.len()))
+                .collect();
+            let rows =
+                utils::wrap_rows(paragraph, &carets, wrap_width, self.wrap == TextWrap::Word);
+
+            for (r, &start) in rows.iter().enumerate() {
+                let end = rows.get(r + 1).copied().unwrap_or(carets.len() - 1);
+                let text = paragraph[bytes[start]..bytes[end]].trim_end();
+                let end = start + text.chars().count();
+                lines.push(Line {
+                    text: text.to_string(),
+                    width: carets[end] - carets[start],
+                    wrapped: r + 1 < rows.len(),
+                });
+            }
+        }
+
+        let mut cut = false;
+        if let Some(max_lines) = self.max_lines {
+            cut = lines.len() > max_lines;
+            lines.truncate(max_lines);
+        }
+
+        if self.ellipsis {
+            let last = lines.len().saturating_sub(1);
+            for (i, line) in lines.iter_mut().enumerate() {
+                if (width > 0.0 && line.width > width) || (cut && i == last) {
+                    *line = self.truncate(&line.text, width);
+                }
+            }
+        }
+        lines
+    }
+
+    /// The longest start of `text` that fits in `width` with an ellipsis after it.
+    fn truncate(&self, text: &str, width: f32) -> Line {
+        let carets = utils::styled_caret_positions(text, &self.style);
+        let ellipsis_w = *utils::styled_caret_positions(ELLIPSIS, &self.style)
+            .last()
+            .unwrap();
+
+        let fits = if width > 0.0 {
+            carets
+                .iter()
+                .rposition(|&x| x + ellipsis_w <= width)
+                .unwrap_or(0)
+        } else {
+            carets.len() - 1
+        };
+        let end = text.char_indices().nth(fits).map_or(text.len(), |(i, _)| i);
+        let kept = text[..end].trim_end();
+        let kept_w = carets[kept.chars().count()];
+
+        Line {
+            text: format!("{kept}{ELLIPSIS}"),
+            width: kept_w + ellipsis_w,
+            wrapped: false,
+        }
+    }
+
+    /// Draws `text` word by word, sharing `free` pixels between the gaps.
+    fn draw_justified(&self, scope: &mut RenderScope, text: &str, y: i32, free: f32) {
+        let carets = utils::styled_caret_positions(text, &self.style);
+        // (char index, byte start, byte end) of every word.
+        let mut words: Vec<(usize, usize, usize)> = Vec::new();
+        let mut in_word = false;
+        for (n, (b, c)) in text.char_indices().enumerate() {
+            if c.is_whitespace() {
+                if in_word {
+                    words.last_mut().unwrap().2 = b;
+                }
+                in_word = false;
+            } else if !in_word {
+                words.push((n, b, text.len()));
+                in_word = true;
+            }
+        }
+        let gap = free / words.len().saturating_sub(1).max(1) as f32;
+
+        for (i, (index, start, end)) in words.into_iter().enumerate() {
+            let x = carets[index] + gap * i as f32;
+            scope.draw_styled_text(x.round() as i32, y, &text[start..end], &self.style);
+        }
+    }
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            style: TextStyle::default(),
+            inherit: Inherit::ALL,
+            align: TextAlign::default(),
+            wrap: TextWrap::default(),
+            line_height: 1.0,
+            max_width: None,
+            max_lines: None,
+            ellipsis: false,
+        }
+    }
+}
+
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Text::new(text)
+    }
+}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
+    }
+}
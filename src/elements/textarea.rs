@@ -0,0 +1,785 @@
+use std::sync::Arc;
+
+use minifb::Key;
+
+use crate::{
+    elements::{
+        div::DivStyle,
+        input::{
+            line_height, next_char, next_word, prev_char, prev_word, OnChange, TextInputStyle,
+            CLIPBOARD,
+        },
+        scroll::{Scroll, ScrollbarStyle},
+    },
+    events::{CharInput, Click, Drag, Event, FocusIn, FocusOut, KeyDown, KeyUp, Modifiers},
+    render::RenderScope,
+    style::{Inherit, TextStyle},
+    utils,
+    widget::{Element, Widget},
+};
+
+/// A position in a [`TextArea`] as `(line, byte offset in the line)`.
+pub type TextPosition = (usize, usize);
+
+struct Line {
+    text: String,
+    /// Kept only while the line is in view; cleared when its text or the wrap width changes.
+    layout: Option<LineLayout>,
+    /// Visual rows of the line, kept after `layout` is dropped and cleared with it.
+    rows: Option<usize>,
+}
+
+struct LineLayout {
+    /// Caret x offset before every char, plus the end of the line.
+    carets: Vec<f32>,
+    /// Byte offset of every entry in `carets`.
+    bytes: Vec<usize>,
+    /// Char index each visual row starts at, the first one always 0.
+    rows: Vec<usize>,
+}
+
+/// A multi-line editable text field with soft wrapping.
+///
+/// Lines are wrapped lazily: only the lines in view keep their layout, the
+/// rest only their row count, which is counted without keeping a layout.
+/// Both are redone for edited lines and when the width or text style changes.
+/// Wheel and scrollbar input come from
+/// [`ScrollExtension`](crate::extensions::scroll::ScrollExtension) through
+/// the shared [`Scroll`] component, keys from
+/// [`KeyboardExtension`](crate::extensions::keyboard::KeyboardExtension).
+/// Text properties are inherited as for a
+/// [`TextInput`](crate::elements::input::TextInput).
+pub struct TextArea {
+    pub placeholder: String,
+    pub style: TextInputStyle,
+    pub inherit: Inherit,
+    pub background: DivStyle,
+    pub scrollbar: ScrollbarStyle,
+    pub scroll: Scroll,
+    lines: Vec<Line>,
+    caret: TextPosition,
+    anchor: Option<TextPosition>,
+    /// Horizontal position kept while moving up and down.
+    goal_x: Option<f32>,
+    /// Scroll the caret into view on the next render.
+    reveal: bool,
+    focused: bool,
+    modifiers: Modifiers,
+    /// The text style of the last render, after inheriting.
+    text_style: TextStyle,
+    line_height: usize,
+    wrap_width: f32,
+    /// The first visual row of every line, plus the total row count.
+    row_starts: Option<Vec<usize>>,
+    /// Rows moved by Page Up / Page Down.
+    page_rows: usize,
+}
+
+impl Element for TextArea {
+    fn render(&mut self, scope: &mut RenderScope) {
+        let text_style = self.style.resolve(scope, self.inherit);
+        let line_h = line_height(&text_style).max(1);
+        self.line_height = line_h;
+        let pad = self.style.padding;
+
+        let (w, h) = scope.get_size_or_parent();
+        scope.reserve(w, h);
+        let background = scope.class_style().fill_div(&self.background).into_owned();
+        background.draw(scope, w, h);
+
+        // Always leave room for the scrollbar so it appearing doesn't rewrap everything.
+        let wrap_width = w.saturating_sub(pad * 2 + self.scrollbar.width) as f32;
+        if wrap_width != self.wrap_width || text_style != self.text_style {
+            self.wrap_width = wrap_width;
+            self.text_style = text_style;
+            self.lines.iter_mut().for_each(Line::invalidate);
+            self.row_starts = None;
+        }
+        self.page_rows = (h.saturating_sub(pad * 2) / line_h).max(1);
+
+        let caret_top = if self.reveal {
+            let line = self.caret.0;
+            Some((self.caret_row() + self.row_starts()[line]) * line_h)
+        } else {
+            None
+        };
+        let total_rows = *self.row_starts().last().unwrap();
+        let offset_y = {
+            let mut state = self.scroll.0.lock().unwrap();
+            state.content = (w, total_rows * line_h + pad * 2);
+            state.viewport = (w, h);
+            state.scrollbar_width = self.scrollbar.width;
+            if let Some(top) = caret_top {
+                let bottom = (top + line_h + pad * 2) as f32;
+                state.offset.1 = state.offset.1.min(top as f32).max(bottom - h as f32);
+                state.velocity = (0.0, 0.0);
+            }
+            state.step();
+            state.offset.1.round() as i32
+        };
+        self.reveal = false;
+
+        background.push_clip(scope, w, h);
+
+        if self.is_empty() {
+            let placeholder = TextStyle {
+                color: self.style.placeholder_color.into(),
+                ..self.text_style.clone()
+            };
+            scope.draw_styled_text(
+                pad as i32,
+                pad as i32 - offset_y,
+                &self.placeholder,
+                &placeholder,
+            );
+        }
+
+        let selection = self.selection();
+        // Start at the line holding the first row in view.
+        let first_row = (offset_y - pad as i32).max(0) as usize / line_h;
+        let starts = self.row_starts();
+        let first = starts.partition_point(|&s| s <= first_row).saturating_sub(1);
+        let mut y = pad as i32 - offset_y + (starts[first] * line_h) as i32;
+        let mut last = first;
+        for i in first..self.lines.len() {
+            if y >= h as i32 {
+                break;
+            }
+            last = i + 1;
+
+            self.ensure_layout(i);
+            let line = &self.lines[i];
+            let layout = line.layout.as_ref().unwrap();
+            let chars = layout.carets.len() - 1;
+
+            // Selected char range in this line, with a little extra for a selected newline.
+            let selected = selection.and_then(|(a, b)| {
+                (a.0..=b.0).contains(&i).then(|| {
+                    let start = if a.0 == i { layout.index(a.1) } else { 0 };
+                    let end = if b.0 == i { layout.index(b.1) } else { chars };
+                    (start, end, b.0 > i)
+                })
+            });
+
+            for (r, &start) in layout.rows.iter().enumerate() {
+                let last = r + 1 == layout.rows.len();
+                let end = layout.rows.get(r + 1).copied().unwrap_or(chars);
+                let x0 = layout.carets[start];
+                let row_y = y + (r * line_h) as i32;
+
+                if row_y + line_h as i32 > 0 && row_y < h as i32 {
+                    if let Some((s, e, newline)) = selected {
+                        let (s, e) = (s.max(start), e.min(end));
+                        let extra = if newline && last {
+                            self.text_style.size / 3.0
+                        } else {
+                            0.0
+                        };
+                        if s < e || (s == e && extra > 0.0 && s == end) {
+                            scope.draw_rect(
+                                pad as i32 + (layout.carets[s] - x0).round() as i32,
+                                row_y,
+                                (layout.carets[e] - layout.carets[s] + extra).round() as usize,
+                                line_h,
+                                self.style.selection_color,
+                            );
+                        }
+                    }
+
+                    let text = &line.text[layout.bytes[start]..layout.bytes[end]];
+                    scope.draw_styled_text(pad as i32, row_y, text, &self.text_style);
+                }
+            }
+
+            if self.focused && self.caret.0 == i {
+                let index = layout.index(self.caret.1);
+                let r = layout.row_of(index);
+                scope.draw_rect(
+                    pad as i32
+                        + (layout.carets[index] - layout.carets[layout.rows[r]]).round() as i32,
+                    y + (r * line_h) as i32,
+                    2,
+                    line_h,
+                    self.style.caret_color,
+                );
+            }
+
+            y += (layout.rows.len() * line_h) as i32;
+        }
+
+        // Lines out of view only keep their row count.
+        for (i, line) in self.lines.iter_mut().enumerate() {
+            if !(first..last).contains(&i) {
+                if let Some(layout) = line.layout.take() {
+                    line.rows = Some(layout.rows.len());
+                }
+            }
+        }
+
+        if let Some((thumb_y, len)) = self.scroll.thumb() {
+            let x = w.saturating_sub(self.scrollbar.width) as i32;
+            if let Some(color) = self.scrollbar.track_color {
+                scope.draw_rect(x, 0, self.scrollbar.width, h, color);
+            }
+            scope.draw_rect_rounded(
+                x,
+                thumb_y,
+                self.scrollbar.width,
+                len,
+                self.scrollbar.width / 2,
+                self.scrollbar.thumb_color,
+            );
+        }
+        scope.pop_clip();
+    }
+
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        scope.get_size_or_parent()
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn event(&mut self, widget: &Arc<Widget>, event: &dyn Event) {
+        self.clamp_caret();
+        let changed = if let Some(click) = event.as_any().downcast_ref::<Click>() {
+            // Presses on the scrollbar belong to ScrollExtension.
+            let on_scrollbar = self.scroll.thumb().is_some() && {
+                let width = self.scroll.0.lock().unwrap().viewport.0;
+                click.x >= width.saturating_sub(self.scrollbar.width) as f32
+            };
+            if !on_scrollbar {
+                let target = self.position_at(click.x, click.y);
+                self.goal_x = None;
+                self.move_caret(target, self.modifiers.shift);
+                self.anchor.get_or_insert(self.caret);
+            }
+            false
+        } else if let Some(drag) = event.as_any().downcast_ref::<Drag>() {
+            if self.anchor.is_some() {
+                self.caret = self.position_at(drag.x, drag.y);
+                self.goal_x = None;
+                self.reveal = true;
+            }
+            false
+        } else if event.as_any().is::<FocusIn>() {
+            self.focused = true;
+            false
+        } else if event.as_any().is::<FocusOut>() {
+            self.focused = false;
+            self.anchor = None;
+            false
+        } else if let Some(e) = event.as_any().downcast_ref::<KeyUp>() {
+            self.modifiers = e.modifiers;
+            false
+        } else if let Some(CharInput(c)) = event.as_any().downcast_ref::<CharInput>() {
+            if self.modifiers.command() && !self.modifiers.alt {
+                false
+            } else {
+                self.insert(&c.to_string());
+                true
+            }
+        } else if let Some(e) = event.as_any().downcast_ref::<KeyDown>() {
+            self.modifiers = e.modifiers;
+            self.key_down(e)
+        } else {
+            false
+        };
+
+        if changed {
+            if let Some(on_change) = widget.get::<OnChange>() {
+                (on_change.0)(widget, &self.text());
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl TextArea {
+    pub fn new(placeholder: &str) -> Self {
+        Self {
+            placeholder: placeholder.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn value(mut self, value: &str) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets the whole style, so nothing is inherited.
+    pub fn style(mut self, style: TextInputStyle) -> Self {
+        self.style = style;
+        self.inherit = Inherit::NONE;
+        self
+    }
+
+    /// Replaces the text without firing [`OnChange`] and moves the caret to the start.
+    pub fn set_value(&mut self, value: &str) {
+        self.lines = value
+            .split('\n')
+            .map(|l| Line::new(l.trim_end_matches('\r')))
+            .collect();
+        self.row_starts = None;
+        self.caret = (0, 0);
+        self.anchor = None;
+        self.goal_x = None;
+        self.reveal = true;
+    }
+
+    /// The whole text, lines joined with `\n`.
+    pub fn text(&self) -> String {
+        self.text_range((0, 0), self.end())
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn line(&self, line: usize) -> Option<&str> {
+        self.lines.get(line).map(|l| l.text.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].text.is_empty()
+    }
+
+    pub fn caret(&self) -> TextPosition {
+        self.caret
+    }
+
+    /// Moves the caret, dropping the selection, and scrolls it into view.
+    pub fn set_caret(&mut self, pos: TextPosition) {
+        self.anchor = None;
+        self.caret = pos;
+        self.clamp_caret();
+        self.reveal = true;
+    }
+
+    /// The selected range, if it is not empty.
+    pub fn selection(&self) -> Option<(TextPosition, TextPosition)> {
+        let anchor = self.anchor?;
+        if anchor == self.caret {
+            None
+        } else {
+            Some((anchor.min(self.caret), anchor.max(self.caret)))
+        }
+    }
+
+    pub fn selected_text(&self) -> String {
+        self.selection()
+            .map(|(a, b)| self.text_range(a, b))
+            .unwrap_or_default()
+    }
+
+    /// Replaces the selection, or inserts at the caret, with `text`.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let text: String = text
+            .chars()
+            .filter(|&c| c == '\n' || !c.is_control())
+            .collect();
+
+        let (line, col) = self.caret;
+        let tail = self.lines[line].text.split_off(col);
+        let mut pieces = text.split('\n');
+        self.lines[line].text.push_str(pieces.next().unwrap_or(""));
+        self.lines[line].invalidate();
+
+        let mut last = line;
+        for piece in pieces {
+            last += 1;
+            self.lines.insert(last, Line::new(piece));
+        }
+        self.caret = (last, self.lines[last].text.len());
+        self.lines[last].text.push_str(&tail);
+        self.lines[last].invalidate();
+        self.row_starts = None;
+        self.goal_x = None;
+        self.reveal = true;
+    }
+
+    /// Returns whether the text changed.
+    fn key_down(&mut self, e: &KeyDown) -> bool {
+        let m = e.modifiers;
+        let word = m.ctrl || m.alt;
+
+        match e.key {
+            Key::Left | Key::Right | Key::Home | Key::End => {
+                let target = match (e.key, self.selection()) {
+                    (Key::Left, Some((start, _))) if !m.shift => start,
+                    (Key::Right, Some((_, end))) if !m.shift => end,
+                    (Key::Left, _) => self.prev_pos(self.caret, word),
+                    (Key::Right, _) => self.next_pos(self.caret, word),
+                    (Key::Home, _) if m.ctrl => (0, 0),
+                    (Key::End, _) if m.ctrl => self.end(),
+                    (Key::Home, _) => self.row_start(),
+                    _ => self.row_end(),
+                };
+                self.goal_x = None;
+                self.move_caret(target, m.shift);
+                false
+            }
+            Key::Up | Key::Down | Key::PageUp | Key::PageDown => {
+                let rows = match e.key {
+                    Key::PageUp | Key::PageDown => self.page_rows,
+                    _ => 1,
+                };
+                let up = matches!(e.key, Key::Up | Key::PageUp);
+                let target = self.vertical(rows, up);
+                self.move_caret(target, m.shift);
+                false
+            }
+            Key::Enter | Key::NumPadEnter => {
+                self.insert("\n");
+                true
+            }
+            Key::Backspace => {
+                if self.selection().is_none() {
+                    self.anchor = Some(self.prev_pos(self.caret, word));
+                }
+                self.delete_selection()
+            }
+            Key::Delete => {
+                if self.selection().is_none() {
+                    self.anchor = Some(self.next_pos(self.caret, word));
+                }
+                self.delete_selection()
+            }
+            Key::A if m.command() => {
+                self.anchor = Some((0, 0));
+                self.caret = self.end();
+                false
+            }
+            Key::C if m.command() => {
+                if self.selection().is_some() {
+                    *CLIPBOARD.lock().unwrap() = self.selected_text();
+                }
+                false
+            }
+            Key::X if m.command() => {
+                if self.selection().is_some() {
+                    *CLIPBOARD.lock().unwrap() = self.selected_text();
+                }
+                self.delete_selection()
+            }
+            Key::V if m.command() => {
+                let text = CLIPBOARD.lock().unwrap().clone();
+                self.insert(&text);
+                !text.is_empty()
+            }
+            _ => false,
+        }
+    }
+
+    fn move_caret(&mut self, target: TextPosition, extend: bool) {
+        if extend {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
+        }
+        self.caret = target;
+        self.reveal = true;
+    }
+
+    /// Returns whether anything was deleted.
+    fn delete_selection(&mut self) -> bool {
+        let deleted = match self.selection() {
+            Some(((l0, c0), (l1, c1))) => {
+                if l0 == l1 {
+                    self.lines[l0].text.replace_range(c0..c1, "");
+                } else {
+                    let tail = self.lines[l1].text[c1..].to_string();
+                    self.lines[l0].text.truncate(c0);
+                    self.lines[l0].text.push_str(&tail);
+                    self.lines.drain(l0 + 1..=l1);
+                }
+                self.lines[l0].invalidate();
+                self.row_starts = None;
+                self.caret = (l0, c0);
+                self.goal_x = None;
+                self.reveal = true;
+                true
+            }
+            None => false,
+        };
+        self.anchor = None;
+        deleted
+    }
+
+    fn text_range(&self, a: TextPosition, b: TextPosition) -> String {
+        if a.0 == b.0 {
+            return self.lines[a.0].text[a.1..b.1].to_string();
+        }
+        let mut out = self.lines[a.0].text[a.1..].to_string();
+        for line in &self.lines[a.0 + 1..b.0] {
+            out.push('\n');
+            out.push_str(&line.text);
+        }
+        out.push('\n');
+        out.push_str(&self.lines[b.0].text[..b.1]);
+        out
+    }
+
+    fn end(&self) -> TextPosition {
+        let last = self.lines.len() - 1;
+        (last, self.lines[last].text.len())
+    }
+
+    /// One char or word back, moving to the end of the previous line at a line start.
+    fn prev_pos(&self, (line, col): TextPosition, word: bool) -> TextPosition {
+        if col == 0 {
+            return match line {
+                0 => (0, 0),
+                _ => (line - 1, self.lines[line - 1].text.len()),
+            };
+        }
+        let text = &self.lines[line].text;
+        let col = if word {
+            prev_word(text, col)
+        } else {
+            prev_char(text, col)
+        };
+        (line, col)
+    }
+
+    /// One char or word forward, moving to the start of the next line at a line end.
+    fn next_pos(&self, (line, col): TextPosition, word: bool) -> TextPosition {
+        let text = &self.lines[line].text;
+        if col == text.len() {
+            return if line + 1 < self.lines.len() {
+                (line + 1, 0)
+            } else {
+                (line, col)
+            };
+        }
+        let col = if word {
+            next_word(text, col)
+        } else {
+            next_char(text, col)
+        };
+        (line, col)
+    }
+
+    fn row_start(&mut self) -> TextPosition {
+        let (line, col) = self.caret;
+        let layout = self.ensure_layout(line);
+        let start = layout.rows[layout.row_of(layout.index(col))];
+        (line, layout.bytes[start])
+    }
+
+    /// End of the caret's visual row. On a wrapped row that is before its last
+    /// char, as the end itself is drawn at the start of the next row.
+    fn row_end(&mut self) -> TextPosition {
+        let (line, col) = self.caret;
+        let layout = self.ensure_layout(line);
+        let r = layout.row_of(layout.index(col));
+        let end = match layout.rows.get(r + 1) {
+            Some(&next) if next > layout.rows[r] + 1 => next - 1,
+            Some(&next) => next,
+            None => layout.carets.len() - 1,
+        };
+        (line, layout.bytes[end])
+    }
+
+    /// The position `rows` visual rows up or down, keeping the horizontal position.
+    fn vertical(&mut self, rows: usize, up: bool) -> TextPosition {
+        let (mut line, col) = self.caret;
+        let layout = self.ensure_layout(line);
+        let index = layout.index(col);
+        let mut row = layout.row_of(index);
+        let current_x = layout.carets[index] - layout.carets[layout.rows[row]];
+        let x = *self.goal_x.get_or_insert(current_x);
+
+        for _ in 0..rows {
+            if up {
+                if row > 0 {
+                    row -= 1;
+                } else if line > 0 {
+                    line -= 1;
+                    row = self.ensure_layout(line).rows.len() - 1;
+                } else {
+                    return (0, 0);
+                }
+            } else if row + 1 < self.ensure_layout(line).rows.len() {
+                row += 1;
+            } else if line + 1 < self.lines.len() {
+                line += 1;
+                row = 0;
+            } else {
+                return self.end();
+            }
+        }
+
+        let layout = self.ensure_layout(line);
+        (line, layout.bytes[layout.nearest(row, x)])
+    }
+
+    /// The position under a point relative to the element.
+    fn position_at(&mut self, x: f32, y: f32) -> TextPosition {
+        let pad = self.style.padding as f32;
+        let offset_y = self.scroll.offset().1;
+        let row = ((y - pad + offset_y) / self.line_height.max(1) as f32).max(0.0) as usize;
+
+        let starts = self.row_starts();
+        if row >= *starts.last().unwrap() {
+            return self.end();
+        }
+        let line = starts.partition_point(|&s| s <= row) - 1;
+        let row = row - starts[line];
+        let layout = self.ensure_layout(line);
+        (line, layout.bytes[layout.nearest(row, x - pad)])
+    }
+
+    /// Visual row of the caret within its line.
+    fn caret_row(&mut self) -> usize {
+        let (line, col) = self.caret;
+        let layout = self.ensure_layout(line);
+        layout.row_of(layout.index(col))
+    }
+
+    /// The first visual row of every line, plus the total, counting the
+    /// rows of lines not counted since they last changed.
+    fn row_starts(&mut self) -> &[usize] {
+        if self.row_starts.is_none() {
+            let (style, width) = (&self.text_style, self.wrap_width);
+            let mut starts = Vec::with_capacity(self.lines.len() + 1);
+            let mut total = 0;
+            starts.push(0);
+            for line in &mut self.lines {
+                total += match &line.layout {
+                    Some(layout) => layout.rows.len(),
+                    None => *line
+                        .rows
+                        .get_or_insert_with(|| LineLayout::count_rows(&line.text, style, width)),
+                };
+                starts.push(total);
+            }
+            self.row_starts = Some(starts);
+        }
+        self.row_starts.as_deref().unwrap()
+    }
+
+    fn ensure_layout(&mut self, line: usize) -> &LineLayout {
+        let (style, width) = (&self.text_style, self.wrap_width);
+        let line = &mut self.lines[line];
+        line.layout
+            .get_or_insert_with(|| LineLayout::new(&line.text, style, width))
+    }
+
+    /// Keeps the caret and anchor valid after the lines were changed.
+    fn clamp_caret(&mut self) {
+        let valid = |(line, col): TextPosition| {
+            self.lines
+                .get(line)
+                .is_some_and(|l| col <= l.text.len() && l.text.is_char_boundary(col))
+        };
+        if !valid(self.caret) {
+            self.caret = self.end();
+        }
+        if self.anchor.is_some_and(|a| !valid(a)) {
+            self.anchor = None;
+        }
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self {
+            placeholder: String::new(),
+            style: TextInputStyle::default(),
+            inherit: Inherit::ALL,
+            background: DivStyle::default(),
+            scrollbar: ScrollbarStyle::default(),
+            scroll: Scroll::default(),
+            lines: vec![Line::new("")],
+            caret: (0, 0),
+            anchor: None,
+            goal_x: None,
+            reveal: false,
+            focused: false,
+            modifiers: Modifiers::default(),
+            text_style: TextStyle::default(),
+            line_height: 0,
+            wrap_width: 0.0,
+            row_starts: None,
+            page_rows: 1,
+        }
+    }
+}
+
+impl Line {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            layout: None,
+            rows: None,
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.layout = None;
+        self.rows = None;
+    }
+}
+
+impl LineLayout {
+    /// Breaks after the last whitespace that fits in `width`, or mid-word if there is none.
+    fn new(text: &str, style: &TextStyle, width: f32) -> Self {
+        let carets = utils::styled_caret_positions(text, style);
+        let bytes: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .collect();
+        let rows = utils::wrap_rows(text, &carets, width, true);
+
+        Self {
+            carets,
+            bytes,
+            rows,
+        }
+    }
+
+    /// The number of visual rows `text` wraps to, without keeping its layout.
+    fn count_rows(text: &str, style: &TextStyle, width: f32) -> usize {
+        let carets = utils::styled_caret_positions(text, style);
+        utils::wrap_rows(text, &carets, width, true).len()
+    }
+
+    fn index(&self, byte: usize) -> usize {
+        self.bytes.partition_point(|&b| b < byte)
+    }
+
+    fn row_of(&self, index: usize) -> usize {
+        self.rows.partition_point(|&r| r <= index).saturating_sub(1)
+    }
+
+    /// The char index in visual `row` closest to `x` from the row's start.
+    fn nearest(&self, row: usize, x: f32) -> usize {
+        let start = self.rows[row];
+        // A wrapped row can't hold the caret at its end, that is the next row's start.
+        let end = match self.rows.get(row + 1) {
+            Some(&next) => next - 1,
+            None => self.carets.len() - 1,
+        }
+        .max(start);
+        let x0 = self.carets[start];
+        (start..=end)
+            .min_by(|&a, &b| {
+                let da = (self.carets[a] - x0 - x).abs();
+                let db = (self.carets[b] - x0 - x).abs();
+                da.total_cmp(&db)
+            })
+            .unwrap_or(start)
+    }
+}
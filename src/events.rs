@@ -0,0 +1,68 @@
+use std::any::Any;
+
+use minifb::Key;
+
+use crate::event;
+
+/// Something delivered to an element through [`Element::event`](crate::widget::Element::event).
+pub trait Event {
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+event!(KeyDown {
+    pub key: Key,
+    /// Set when the key is held and the event comes from key repeat.
+    pub repeat: bool,
+    pub modifiers: Modifiers,
+});
+
+event!(KeyUp {
+    pub key: Key,
+    pub modifiers: Modifiers,
+});
+
+event!(CharInput(pub char));
+
+event!(
+    /// A left click, relative to the clicked widget's top-left corner.
+    Click { pub x: f32, pub y: f32 }
+);
+
+event!(
+    /// The cursor while the left button stays held after a [`Click`], sent
+    /// every frame to the clicked widget and relative to it.
+    Drag { pub x: f32, pub y: f32 }
+);
+
+event!(FocusIn);
+
+event!(FocusOut);
+
+impl Modifiers {
+    pub fn from_keys(keys: &[Key]) -> Self {
+        let held = |a: Key, b: Key| keys.contains(&a) || keys.contains(&b);
+        Self {
+            shift: held(Key::LeftShift, Key::RightShift),
+            ctrl: held(Key::LeftCtrl, Key::RightCtrl),
+            alt: held(Key::LeftAlt, Key::RightAlt),
+            logo: held(Key::LeftSuper, Key::RightSuper),
+        }
+    }
+
+    /// Ctrl, or Cmd on macOS.
+    pub fn command(&self) -> bool {
+        if cfg!(target_os = "macos") {
+            self.logo
+        } else {
+            self.ctrl
+        }
+    }
+}
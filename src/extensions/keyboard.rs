@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use minifb::{Key, KeyRepeat, MouseButton};
+
+use crate::{
+    backend::Backend,
+    component,
+    events::{CharInput, Event, FocusIn, FocusOut, KeyDown, KeyUp, Modifiers},
+    extensions::{hit_test, Extension},
+    style::RawTransform,
+    widget::Widget,
+};
+
+/// Turns keyboard state into key and text events for the focused widget.
+///
+/// Focus moves with Tab / Shift-Tab through focusable widgets in tree order,
+/// or to the deepest focusable widget under the cursor when it is clicked;
+/// a click on nothing focusable blurs. Code can move it through the
+/// [`Focused`] component.
+/// Events go to the element's [`Element::event`](crate::widget::Element::event)
+/// first, then to the [`OnKeyDown`], [`OnKeyUp`] and [`OnChar`] components.
+pub struct KeyboardExtension {
+    focused: Option<Arc<Widget>>,
+    mouse_down: bool,
+}
+
+component!(
+    /// Marks the focused widget. Setting it on a widget focuses that widget on
+    /// the next frame, and removing it blurs it.
+    Focused
+);
+// Marks a widget as focusable even without key handlers.
+component!(Focusable);
+component!(OnKeyDown(pub fn(&Arc<Widget>, &KeyDown)));
+component!(OnKeyUp(pub fn(&Arc<Widget>, &KeyUp)));
+component!(OnChar(pub fn(&Arc<Widget>, char)));
+
+impl Extension for KeyboardExtension {
+    fn before_render(
+        &mut self,
+        widgets: &[Arc<Widget>],
+        _transform: RawTransform,
+        backend: &dyn Backend,
+    ) {
+        self.sync_focused(widgets);
+
+        let down = backend.get_mouse_down(MouseButton::Left);
+        let pressed = down && !self.mouse_down;
+        self.mouse_down = down;
+
+        if let Some((mx, my)) = backend.get_mouse_pos().filter(|_| pressed) {
+            let hit = hit_test(widgets, mx, my);
+            self.focus(hit.into_iter().rev().find(is_focusable));
+        }
+
+        let modifiers = Modifiers::from_keys(&backend.get_keys());
+        let fresh = backend.get_keys_pressed(KeyRepeat::No);
+
+        for key in backend.get_keys_pressed(KeyRepeat::Yes) {
+            if key == Key::Tab {
+                self.move_focus(widgets, !modifiers.shift);
+                continue;
+            }
+
+            let event = KeyDown {
+                key,
+                repeat: !fresh.contains(&key),
+                modifiers,
+            };
+            if let Some(widget) = &self.focused {
+                deliver(widget, &event);
+                if let Some(on_key_down) = widget.get::<OnKeyDown>() {
+                    (on_key_down.0)(widget, &event);
+                }
+            }
+        }
+
+        for key in backend.get_keys_released() {
+            let event = KeyUp { key, modifiers };
+            if let Some(widget) = &self.focused {
+                deliver(widget, &event);
+                if let Some(on_key_up) = widget.get::<OnKeyUp>() {
+                    (on_key_up.0)(widget, &event);
+                }
+            }
+        }
+
+        for c in backend.get_chars() {
+            if c.is_control() {
+                continue;
+            }
+            if let Some(widget) = &self.focused {
+                deliver(widget, &CharInput(c));
+                if let Some(on_char) = widget.get::<OnChar>() {
+                    (on_char.0)(widget, c);
+                }
+            }
+        }
+    }
+}
+
+impl KeyboardExtension {
+    pub fn new() -> Self {
+        KeyboardExtension {
+            focused: None,
+            mouse_down: false,
+        }
+    }
+
+    pub fn focused(&self) -> Option<&Arc<Widget>> {
+        self.focused.as_ref()
+    }
+
+    /// Moves focus to `widget`, updating [`Focused`] and sending focus events.
+    pub fn focus(&mut self, widget: Option<Arc<Widget>>) {
+        if let (Some(a), Some(b)) = (&self.focused, &widget) {
+            if Arc::ptr_eq(a, b) {
+                return;
+            }
+        }
+
+        if let Some(old) = self.focused.take() {
+            old.remove_component::<Focused>();
+            deliver(&old, &FocusOut);
+        }
+        if let Some(new) = widget {
+            new.set_component(Focused);
+            deliver(&new, &FocusIn);
+            self.focused = Some(new);
+        }
+    }
+
+    /// Follows [`Focused`] components set or removed since the last frame.
+    /// If several were set, the last one in tree order wins.
+    fn sync_focused(&mut self, widgets: &[Arc<Widget>]) {
+        if let Some(old) = self.focused.take_if(|w| !w.has::<Focused>()) {
+            deliver(&old, &FocusOut);
+        }
+        let mut requested: Vec<Arc<Widget>> = widgets
+            .iter()
+            .flat_map(|w| w.walk())
+            .filter(|w| w.has::<Focused>())
+            .filter(|w| !self.focused.as_ref().is_some_and(|f| Arc::ptr_eq(f, w)))
+            .collect();
+        if let Some(widget) = requested.pop() {
+            for other in requested {
+                other.remove_component::<Focused>();
+            }
+            self.focus(Some(widget));
+        }
+    }
+
+    fn move_focus(&mut self, widgets: &[Arc<Widget>], forward: bool) {
+        let order: Vec<Arc<Widget>> = widgets
+            .iter()
+            .flat_map(|w| w.walk())
+            .filter(is_focusable)
+            .collect();
+        if order.is_empty() {
+            return;
+        }
+
+        let current = self
+            .focused
+            .as_ref()
+            .and_then(|f| order.iter().position(|w| Arc::ptr_eq(w, f)));
+        let next = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+            (Some(i), true) => (i + 1) % order.len(),
+            (Some(i), false) => (i + order.len() - 1) % order.len(),
+        };
+        self.focus(Some(order[next].clone()));
+    }
+}
+
+impl Default for KeyboardExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_focusable(widget: &Arc<Widget>) -> bool {
+    widget.has::<Focusable>()
+        || widget.has::<OnKeyDown>()
+        || widget.has::<OnKeyUp>()
+        || widget.has::<OnChar>()
+        || widget.0.lock().unwrap().focusable()
+}
+
+fn deliver(widget: &Arc<Widget>, event: &dyn Event) {
+    widget.0.lock().unwrap().event(widget, event);
+}
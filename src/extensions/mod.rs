@@ -1,21 +1,51 @@
+pub mod keyboard;
 pub mod mouse;
+pub mod scroll;
 pub mod tick;
 pub mod velocity;
 
 use std::sync::Arc;
 
-use minifb::Window;
-
-use crate::{style::RawTransform, widget::Widget};
+use crate::{backend::Backend, render::Layout, style::RawTransform, widget::Widget};
 
 pub trait Extension {
-    fn init(&mut self, _widgets: &Vec<Arc<Widget>>) {}
+    fn init(&mut self, _widgets: &[Arc<Widget>]) {}
     fn before_render(
         &mut self,
-        _widgets: &Vec<Arc<Widget>>,
+        _widgets: &[Arc<Widget>],
         _transform: RawTransform,
-        _win: &Window,
+        _backend: &dyn Backend,
     ) {
     }
-    fn render(&mut self, _widget: &Arc<Widget>, _transform: RawTransform, _win: &Window) {}
+    fn render(&mut self, _widget: &Arc<Widget>, _transform: RawTransform, _backend: &dyn Backend) {}
+}
+
+/// The topmost widget under `(x, y)` in the last frame, going by each widget's
+/// [`Layout`], preceded by its ancestors from the top-level widget down.
+/// Empty when nothing was drawn there.
+pub fn hit_test(widgets: &[Arc<Widget>], x: f32, y: f32) -> Vec<Arc<Widget>> {
+    fn visit(
+        widget: &Arc<Widget>,
+        x: f32,
+        y: f32,
+        chain: &mut Vec<Arc<Widget>>,
+        hit: &mut Vec<Arc<Widget>>,
+    ) {
+        chain.push(widget.clone());
+        // Later widgets in the walk are drawn on top of earlier ones.
+        if widget.get::<Layout>().is_some_and(|l| l.contains(x, y)) {
+            hit.clone_from(chain);
+        }
+        let children = widget.0.lock().unwrap().children();
+        for child in &children {
+            visit(child, x, y, chain, hit);
+        }
+        chain.pop();
+    }
+
+    let mut hit = Vec::new();
+    for widget in widgets {
+        visit(widget, x, y, &mut Vec::new(), &mut hit);
+    }
+    hit
 }
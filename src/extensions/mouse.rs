@@ -1,46 +1,71 @@
 use std::sync::Arc;
 
-use minifb::{MouseButton, Window};
+use minifb::MouseButton;
 
-use crate::{component, extensions::Extension, style::RawTransform, widget::Widget};
+use crate::{
+    backend::Backend,
+    component,
+    events::{Click, Drag},
+    extensions::{hit_test, Extension},
+    render::Layout,
+    style::RawTransform,
+    widget::Widget,
+};
 
-pub struct MouseExtension(bool);
+/// Calls [`OnClick`] while the left button is held over a widget or any of
+/// its descendants, sends a [`Click`] event to the deepest widget under the
+/// cursor once per press, and [`Drag`] events to it until the button is released.
+pub struct MouseExtension {
+    down: bool,
+    /// The widget that got the last [`Click`], while the button is held.
+    pressed: Option<Arc<Widget>>,
+}
 
 impl Extension for MouseExtension {
     fn before_render(
         &mut self,
-        _widgets: &Vec<Arc<Widget>>,
+        widgets: &[Arc<Widget>],
         _transform: RawTransform,
-        win: &Window,
+        backend: &dyn Backend,
     ) {
-        if win.get_mouse_down(MouseButton::Left) {
-            if !self.0 {
-                self.0 = true;
-            }
-        } else {
-            self.0 = false;
+        let down = backend.get_mouse_down(MouseButton::Left);
+        let pressed = down && !self.down;
+        self.down = down;
+        if !down {
+            self.pressed = None;
+            return;
         }
-    }
 
-    fn render(&mut self, widget: &Arc<Widget>, transform: RawTransform, win: &Window) {
-        if !self.0 {
+        let Some((mx, my)) = backend.get_mouse_pos() else {
             return;
+        };
+
+        if let Some(widget) = self.pressed.as_ref().filter(|_| !pressed) {
+            if let Some(layout) = widget.get::<Layout>() {
+                let drag = Drag {
+                    x: mx - layout.x as f32,
+                    y: my - layout.y as f32,
+                };
+                widget.0.lock().unwrap().event(widget, &drag);
+            }
+        }
+
+        let hit = hit_test(widgets, mx, my);
+
+        if let (true, Some(widget)) = (pressed, hit.last()) {
+            if let Some(layout) = widget.get::<Layout>() {
+                let click = Click {
+                    x: mx - layout.x as f32,
+                    y: my - layout.y as f32,
+                };
+                widget.0.lock().unwrap().event(widget, &click);
+                self.pressed = Some(widget.clone());
+            }
         }
 
-        if let Some((mx, my)) = win.get_mouse_pos(minifb::MouseMode::Discard) {
-            let (mx, my) = (mx as usize, my as usize);
-            if my >= transform.y
-                && mx < transform.x + transform.width
-                && my < transform.y + transform.height
-            {
-                std::thread::spawn({
-                    let widget = widget.clone();
-                    move || {
-                        if let Some(on_click) = widget.get::<OnClick>() {
-                            (on_click.0)(&widget)
-                        }
-                    }
-                });
+        for widget in hit {
+            if let Some(on_click) = widget.get::<OnClick>() {
+                std::thread::spawn(move || (on_click.0)(&widget));
             }
         }
     }
@@ -48,7 +73,16 @@ impl Extension for MouseExtension {
 
 impl MouseExtension {
     pub fn new() -> Self {
-        MouseExtension(false)
+        MouseExtension {
+            down: false,
+            pressed: None,
+        }
+    }
+}
+
+impl Default for MouseExtension {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
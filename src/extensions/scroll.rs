@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use minifb::MouseButton;
+
+use crate::{
+    backend::Backend,
+    elements::{
+        div::{Div, Overflow},
+        scroll::{Scroll, ScrollView},
+        textarea::TextArea,
+    },
+    extensions::{hit_test, Extension},
+    render::Layout,
+    style::RawTransform,
+    widget::Widget,
+};
+
+/// Scrolls [`ScrollView`]s, [`TextArea`]s and [`Div`]s with [`Overflow::Scroll`]
+/// anywhere in the tree with the mouse wheel and by dragging their scrollbar.
+/// The deepest one under the cursor takes the input.
+pub struct ScrollExtension {
+    /// Pixels scrolled per wheel unit.
+    pub speed: f32,
+    down: bool,
+}
+
+impl Extension for ScrollExtension {
+    fn init(&mut self, widgets: &[Arc<Widget>]) {
+        for widget in widgets.iter().flat_map(|w| w.walk()) {
+            attach_scroll(&widget);
+        }
+    }
+
+    fn before_render(
+        &mut self,
+        widgets: &[Arc<Widget>],
+        _transform: RawTransform,
+        backend: &dyn Backend,
+    ) {
+        let down = backend.get_mouse_down(MouseButton::Left);
+        let pressed = down && !self.down;
+        self.down = down;
+
+        // Widgets added since the last frame need the component too.
+        let mut dragging = false;
+        for widget in widgets.iter().flat_map(|w| w.walk()) {
+            let Some(scroll) = attach_scroll(&widget) else {
+                continue;
+            };
+            let mut state = scroll.0.lock().unwrap();
+            let Some(grab) = state.drag else {
+                continue;
+            };
+            if !down {
+                state.drag = None;
+            } else if let (Some((_, my)), Some(layout)) =
+                (backend.get_mouse_pos(), widget.get::<Layout>())
+            {
+                state.drag_thumb_to(my - layout.y as f32 - grab);
+                dragging = true;
+            }
+        }
+
+        let Some((mx, my)) = backend.get_mouse_pos() else {
+            return;
+        };
+        if dragging {
+            return;
+        }
+
+        // The deepest scrollable widget under the cursor takes the input.
+        let hit = hit_test(widgets, mx, my);
+        let Some((scroll, layout)) = hit
+            .iter()
+            .rev()
+            .find_map(|w| Some((w.get::<Scroll>()?, w.get::<Layout>()?)))
+        else {
+            return;
+        };
+        let (lx, ly) = (mx - layout.x as f32, my - layout.y as f32);
+
+        let mut state = scroll.0.lock().unwrap();
+        if pressed && lx >= layout.width.saturating_sub(state.scrollbar_width) as f32 {
+            if let Some((y, len)) = state.thumb() {
+                let (top, len) = (y as f32, len as f32);
+                // Clicking the track centers the thumb under the cursor before dragging.
+                let grab = if ly >= top && ly < top + len {
+                    ly - top
+                } else {
+                    len / 2.0
+                };
+                state.drag = Some(grab);
+                state.drag_thumb_to(ly - grab);
+                return;
+            }
+        }
+        drop(state);
+
+        if let Some((dx, dy)) = backend.get_scroll_wheel() {
+            scroll.scroll_by(-dx * self.speed, -dy * self.speed);
+        }
+    }
+}
+
+impl ScrollExtension {
+    pub fn new() -> Self {
+        ScrollExtension {
+            speed: 40.0,
+            down: false,
+        }
+    }
+}
+
+impl Default for ScrollExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shares the scroll state of a [`ScrollView`], [`TextArea`] or scrolling
+/// [`Div`] through its widget's [`Scroll`] component, returning it.
+fn attach_scroll(widget: &Arc<Widget>) -> Option<Scroll> {
+    if let Some(scroll) = widget.get::<Scroll>() {
+        return Some(scroll);
+    }
+    let scroll = {
+        let element = widget.0.lock().unwrap();
+        let any = element.as_any();
+        any.downcast_ref::<ScrollView>()
+            .map(|view| view.scroll.clone())
+            .or_else(|| any.downcast_ref::<TextArea>().map(|area| area.scroll.clone()))
+            .or_else(|| {
+                let div = any.downcast_ref::<Div>()?;
+                (div.style.overflow == Overflow::Scroll).then(|| div.scroll.clone())
+            })
+    }?;
+    widget.set_component(scroll.clone());
+    Some(scroll)
+}
@@ -29,9 +29,9 @@ impl VelocityExtension {
 }
 
 impl Extension for VelocityExtension {
-    fn init(&mut self, widgets: &Vec<Arc<Widget>>) {
+    fn init(&mut self, widgets: &[Arc<Widget>]) {
         std::thread::spawn({
-            let widgets = widgets.clone();
+            let widgets = widgets.to_vec();
             move || {
                 let mut tick = 0;
                 loop {
@@ -0,0 +1,281 @@
+use std::{
+    collections::HashMap,
+    fmt, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use once_cell::sync::Lazy;
+
+/// A decoded image, one `0xAARRGGBB` pixel per entry, row by row.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ImageData {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>,
+}
+
+/// How an image is sampled when drawn at a different size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Filter {
+    Nearest,
+    #[default]
+    Bilinear,
+}
+
+/// Decoded pixels kept by the cache before the least recently used images are dropped.
+const CACHE_LIMIT_BYTES: usize = 256 << 20;
+
+#[derive(PartialEq, Eq, Hash)]
+enum CacheKey {
+    Path(PathBuf),
+    /// The encoded bytes themselves, so different images never share an entry.
+    Bytes(Vec<u8>),
+}
+
+#[derive(Default)]
+struct Cache {
+    images: HashMap<CacheKey, (Arc<ImageData>, u64)>,
+    bytes: usize,
+    tick: u64,
+}
+
+impl Cache {
+    fn get(&mut self, key: &CacheKey) -> Option<Arc<ImageData>> {
+        self.tick += 1;
+        let (image, used) = self.images.get_mut(key)?;
+        *used = self.tick;
+        Some(image.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, image: Arc<ImageData>) {
+        self.bytes += image.pixels.len() * 4;
+        if let Some((old, _)) = self.images.insert(key, (image, self.tick)) {
+            self.bytes -= old.pixels.len() * 4;
+        }
+        while self.bytes > CACHE_LIMIT_BYTES && self.images.len() > 1 {
+            let oldest = self.images.values().map(|(_, used)| *used).min();
+            let bytes = &mut self.bytes;
+            self.images.retain(|_, (image, used)| {
+                let keep = Some(*used) != oldest;
+                if !keep {
+                    *bytes -= image.pixels.len() * 4;
+                }
+                keep
+            });
+        }
+    }
+}
+
+static CACHE: Lazy<Mutex<Cache>> = Lazy::new(Default::default);
+
+/// Loads a PNG or BMP file, sharing the decoded image with every earlier load of the same path.
+pub fn load(path: impl AsRef<Path>) -> io::Result<Arc<ImageData>> {
+    let path = path.as_ref();
+    let key = CacheKey::Path(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+    if let Some(image) = CACHE.lock().unwrap().get(&key) {
+        return Ok(image);
+    }
+
+    let image = Arc::new(ImageData::decode(&std::fs::read(path)?)?);
+    CACHE.lock().unwrap().insert(key, image.clone());
+    Ok(image)
+}
+
+/// Decodes a PNG or BMP, sharing the result with earlier calls for the same bytes.
+pub fn from_bytes(bytes: &[u8]) -> io::Result<Arc<ImageData>> {
+    let key = CacheKey::Bytes(bytes.to_vec());
+    if let Some(image) = CACHE.lock().unwrap().get(&key) {
+        return Ok(image);
+    }
+
+    let image = Arc::new(ImageData::decode(bytes)?);
+    CACHE.lock().unwrap().insert(key, image.clone());
+    Ok(image)
+}
+
+/// Forgets every cached image; widgets keep the ones they already hold.
+pub fn clear_cache() {
+    let mut cache = CACHE.lock().unwrap();
+    cache.images.clear();
+    cache.bytes = 0;
+}
+
+impl ImageData {
+    pub fn new(width: usize, height: usize, pixels: Vec<u32>) -> Self {
+        assert_eq!(pixels.len(), width * height, "pixel count must match size");
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Decodes a PNG or BMP, picked by its signature.
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            decode_png(bytes)
+        } else if bytes.starts_with(b"BM") {
+            decode_bmp(bytes)
+        } else {
+            Err(invalid("unsupported image format"))
+        }
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> u32 {
+        self.pixels[y * self.width + x]
+    }
+}
+
+impl fmt::Debug for ImageData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageData")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish_non_exhaustive()
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn decode_png(bytes: &[u8]) -> io::Result<ImageData> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(io::Error::other)?;
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).map_err(io::Error::other)?;
+
+    let channels = info.color_type.samples();
+    let pixels = data[..info.buffer_size()]
+        .chunks(channels)
+        .map(|c| match c.len() {
+            1 => u32::from_be_bytes([0xFF, c[0], c[0], c[0]]),
+            2 => u32::from_be_bytes([c[1], c[0], c[0], c[0]]),
+            3 => u32::from_be_bytes([0xFF, c[0], c[1], c[2]]),
+            _ => u32::from_be_bytes([c[3], c[0], c[1], c[2]]),
+        })
+        .collect();
+
+    Ok(ImageData::new(
+        info.width as usize,
+        info.height as usize,
+        pixels,
+    ))
+}
+
+/// Uncompressed and bitfield BMPs with 1, 4, 8, 16, 24 or 32 bits per pixel.
+fn decode_bmp(bytes: &[u8]) -> io::Result<ImageData> {
+    let u16_at = |i: usize| -> io::Result<u32> {
+        bytes
+            .get(i..i + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as u32)
+            .ok_or_else(|| invalid("truncated BMP"))
+    };
+    let u32_at = |i: usize| -> io::Result<u32> {
+        bytes
+            .get(i..i + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| invalid("truncated BMP"))
+    };
+
+    let data_offset = u32_at(10)? as usize;
+    let header_size = u32_at(14)? as usize;
+    if header_size < 40 {
+        return Err(invalid("unsupported BMP header"));
+    }
+    let width = u32_at(18)? as i32;
+    let height = u32_at(22)? as i32;
+    let bpp = u16_at(28)?;
+    let compression = u32_at(30)?;
+    if width <= 0 || height == 0 {
+        return Err(invalid("invalid BMP size"));
+    }
+    let (width, top_down) = (width as usize, height < 0);
+    let height = height.unsigned_abs() as usize;
+
+    // BI_BITFIELDS and BI_ALPHABITFIELDS store channel masks right after the 40 byte header.
+    let (masks, has_alpha) = match (compression, bpp) {
+        (0, 16) => ([0x7C00, 0x03E0, 0x001F, 0], false),
+        (0, 24 | 32) => ([0xFF0000, 0x00FF00, 0x0000FF, 0xFF000000], bpp == 32),
+        (0, 1 | 4 | 8) => ([0; 4], false),
+        (3 | 6, 16 | 32) => {
+            let alpha = if compression == 6 || header_size >= 56 {
+                u32_at(66)?
+            } else {
+                0
+            };
+            ([u32_at(54)?, u32_at(58)?, u32_at(62)?, alpha], alpha != 0)
+        }
+        _ => return Err(invalid("unsupported BMP encoding")),
+    };
+
+    let palette: Vec<u32> = if bpp <= 8 {
+        let count = match u32_at(46)? {
+            0 => 1 << bpp,
+            n => (n as usize).min(256),
+        };
+        (0..count)
+            .map(|i| u32_at(14 + header_size + i * 4).map(|c| 0xFF000000 | (c & 0xFFFFFF)))
+            .collect::<io::Result<_>>()?
+    } else {
+        Vec::new()
+    };
+
+    // The sizes come from the file, so a crafted header must not overflow them.
+    let stride = width
+        .checked_mul(bpp as usize)
+        .map(|bits| bits.div_ceil(32) * 4)
+        .ok_or_else(|| invalid("invalid BMP size"))?;
+    let end = stride
+        .checked_mul(height)
+        .and_then(|size| size.checked_add(data_offset))
+        .ok_or_else(|| invalid("invalid BMP size"))?;
+    if bytes.len() < end {
+        return Err(invalid("truncated BMP"));
+    }
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let row_y = if top_down { y } else { height - 1 - y };
+        let row = &bytes[data_offset + row_y * stride..][..stride];
+        for x in 0..width {
+            let pixel = match bpp {
+                1 | 4 | 8 => {
+                    let bit = x * bpp as usize;
+                    let byte = row[bit / 8];
+                    let index = (byte >> (8 - bpp as usize - bit % 8)) & ((1 << bpp) - 1) as u8;
+                    palette.get(index as usize).copied().unwrap_or(0xFF000000)
+                }
+                _ => {
+                    let n = bpp as usize / 8;
+                    let mut raw = [0u8; 4];
+                    raw[..n].copy_from_slice(&row[x * n..x * n + n]);
+                    let value = u32::from_le_bytes(raw);
+                    let [r, g, b, a] = masks.map(|m| channel(value, m));
+                    let a = if has_alpha { a } else { 0xFF };
+                    u32::from_be_bytes([a, r, g, b])
+                }
+            };
+            pixels.push(pixel);
+        }
+    }
+
+    // Plenty of 32-bit BMPs leave the alpha byte at zero; show those as opaque.
+    if has_alpha && compression == 0 && pixels.iter().all(|p| p >> 24 == 0) {
+        pixels.iter_mut().for_each(|p| *p |= 0xFF000000);
+    }
+
+    Ok(ImageData::new(width, height, pixels))
+}
+
+/// Extracts the channel under `mask`, scaled to 8 bits.
+fn channel(value: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let max = (mask >> shift) as u64;
+    (((value & mask) >> shift) as u64 * 255 / max) as u8
+}
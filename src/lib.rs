@@ -1,33 +1,62 @@
 use minifb::Window;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use crate::{
+    backend::{Backend, HeadlessBackend, WindowBackend},
     extensions::Extension,
     render::RenderScope,
     style::Transform,
+    theme::{Theme, ThemeHandle},
     widget::{Element, Widget},
 };
 
+pub mod backend;
+pub mod color;
 pub mod elements;
+pub mod events;
 pub mod extensions;
+pub mod image;
 pub mod macros;
+pub mod paint;
 pub mod render;
 pub mod style;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod theme;
 pub mod utils;
 pub mod widget;
 
-pub struct Screen {
-    window: Window,
+pub struct Screen<B: Backend = WindowBackend> {
+    backend: B,
     pub widgets: Vec<Arc<Widget>>,
-    extensions: Vec<Arc<Mutex<Box<dyn Extension>>>>,
+    extensions: Vec<Box<dyn Extension>>,
+    scope: RenderScope,
+    theme: ThemeHandle,
+    initialized: bool,
 }
 
-impl Screen {
+impl Screen<WindowBackend> {
     pub fn new(win: Window) -> Self {
+        Self::with_backend(WindowBackend::new(win))
+    }
+}
+
+impl Screen<HeadlessBackend> {
+    pub fn headless(width: usize, height: usize) -> Self {
+        Self::with_backend(HeadlessBackend::new(width, height))
+    }
+}
+
+impl<B: Backend> Screen<B> {
+    pub fn with_backend(backend: B) -> Self {
+        let (w, h) = backend.get_size();
         Self {
-            window: win,
+            backend,
             widgets: Vec::new(),
             extensions: Vec::new(),
+            scope: RenderScope::new(w, h),
+            theme: ThemeHandle::default(),
+            initialized: false,
         }
     }
 
@@ -37,26 +66,38 @@ impl Screen {
     }
 
     pub fn extension<E: Extension + 'static>(&mut self, ext: E) {
-        self.extensions.push(Arc::new(Mutex::new(Box::new(ext))));
+        self.extensions.push(Box::new(ext));
     }
 
-    pub fn run(&mut self) -> std::io::Result<()> {
-        for elem in &self.widgets {
-            elem.component(Transform::new());
-        }
+    /// Restyles every widget with `theme` from the next frame on.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme.set(theme);
+    }
 
-        for ext in &self.extensions {
-            ext.lock().unwrap().init(&self.widgets);
-        }
+    /// A handle to the screen's theme, for switching it from elsewhere.
+    pub fn theme(&self) -> ThemeHandle {
+        self.theme.clone()
+    }
 
-        let (w, h) = self.window.get_size();
-        let mut scope = RenderScope::new(w, h);
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
 
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    /// The last rendered frame, row-major at the backend's size.
+    pub fn get_buffer(&self) -> &[u32] {
+        self.scope.get_buffer1d()
+    }
+
+    pub fn run(&mut self) -> std::io::Result<()> {
         let mut last_fps_check = std::time::Instant::now();
         let mut frame_count = 0;
 
-        while self.window.is_open() {
-            self.render(&mut scope);
+        while self.backend.is_open() {
+            self.frame()?;
             frame_count += 1;
 
             // Print FPS every second
@@ -70,18 +111,50 @@ impl Screen {
         Ok(())
     }
 
-    fn render(&mut self, scope: &mut RenderScope) {
-        let (w, h) = self.window.get_size();
+    /// Renders and presents a single frame, initializing the screen on the first call.
+    pub fn frame(&mut self) -> std::io::Result<()> {
+        if !self.initialized {
+            self.init();
+        }
+
+        self.render()
+    }
+
+    fn init(&mut self) {
+        self.initialized = true;
+
+        for elem in &self.widgets {
+            elem.component(Transform::new());
+        }
+
+        for ext in &mut self.extensions {
+            ext.init(&self.widgets);
+        }
+    }
+
+    fn render(&mut self) -> std::io::Result<()> {
+        let scope = &mut self.scope;
+        let (w, h) = self.backend.get_size();
         scope.resize_if_needed(w, h);
+        scope.clear_buffer();
 
-        for ext in &self.extensions {
-            ext.lock()
-                .unwrap()
-                .before_render(&self.widgets, scope.get_transform(), &self.window);
+        for ext in &mut self.extensions {
+            ext.before_render(&self.widgets, scope.get_transform(), &self.backend);
+        }
+
+        let theme = self.theme.get();
+        let text = theme.root_text();
+        if let Some(background) = &theme.root.background {
+            scope.clear();
+            scope.set_transform(&Transform::new());
+            scope.draw_rect(0, 0, w, h, background.clone());
+            scope.draw();
         }
 
         for elem in &self.widgets {
             scope.clear();
+            scope.set_widget(elem);
+            scope.apply_theme(elem, Some(theme.clone()), &text);
             if let Some(t) = elem.get() {
                 scope.set_transform(&t);
             }
@@ -92,18 +165,13 @@ impl Screen {
                 scope.set_transform(&t);
             }
 
-            for ext in &self.extensions {
-                ext.lock()
-                    .unwrap()
-                    .render(elem, scope.get_transform(), &self.window);
+            for ext in &mut self.extensions {
+                ext.render(elem, scope.get_transform(), &self.backend);
             }
 
             scope.draw();
         }
 
-        self.window
-            .update_with_buffer(scope.get_buffer1d(), w, h)
-            .unwrap();
-        scope.clear_buffer();
+        self.backend.update_with_buffer(scope.get_buffer1d(), w, h)
     }
 }
@@ -1,6 +1,7 @@
 #[macro_export]
 macro_rules! event {
-    ($name:ident) => {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
         #[derive(Debug, Clone)]
         pub struct $name;
 
@@ -11,7 +12,8 @@ macro_rules! event {
         }
     };
 
-    ($name:ident {$($inner:tt)*}) => {
+    ($(#[$meta:meta])* $name:ident {$($inner:tt)*}) => {
+        $(#[$meta])*
         #[derive(Debug, Clone)]
         pub struct $name {
             $($inner)*
@@ -24,7 +26,8 @@ macro_rules! event {
         }
     };
 
-    ($name:ident ($($inner:tt)*)) => {
+    ($(#[$meta:meta])* $name:ident ($($inner:tt)*)) => {
+        $(#[$meta])*
         #[derive(Debug, Clone)]
         pub struct $name ($($inner)*);
 
@@ -38,7 +41,8 @@ macro_rules! event {
 
 #[macro_export]
 macro_rules! component {
-    ($name:ident) => {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
         #[derive(Debug, Clone)]
         pub struct $name;
 
@@ -53,7 +57,8 @@ macro_rules! component {
         }
     };
 
-    ($name:ident {$($inner:tt)*}) => {
+    ($(#[$meta:meta])* $name:ident {$($inner:tt)*}) => {
+        $(#[$meta])*
         #[derive(Debug, Clone)]
         pub struct $name {
             $($inner)*
@@ -70,7 +75,8 @@ macro_rules! component {
         }
     };
 
-    ($name:ident ($($inner:tt)*)) => {
+    ($(#[$meta:meta])* $name:ident ($($inner:tt)*)) => {
+        $(#[$meta])*
         #[derive(Debug, Clone)]
         pub struct $name ($($inner)*);
 
@@ -98,12 +104,12 @@ macro_rules! component {
 /// - `$method`: The method name on `self` to call when an event occurs.
 ///
 /// # Usage
-/// ```rust
+/// ```rust,ignore
 /// event_handler!(self, events, on_keypress);
 /// ```
 ///
 /// This expands roughly to:
-/// ```rust
+/// ```rust,ignore
 /// let self_ref = self as *mut Self;
 /// events.on(move |event| unsafe { (*self_ref).on_keypress(event) });
 /// ```
@@ -1,18 +1,60 @@
-use minifb::{Window, WindowOptions};
+use std::sync::Arc;
+
+use minifb::{MouseButton, Window, WindowOptions};
 use osgui::{
+    backend::Backend,
+    color::Color,
+    component,
     elements::{
         custom,
         div::{Div, DivStyle},
+        text::Text,
     },
     extensions::{
+        hit_test,
         mouse::{MouseExtension, OnClick},
         velocity::{Velocity, VelocityExtension},
+        Extension,
     },
     render::RenderScope,
-    style::Transform,
+    style::{BoxShadow, RawTransform, Transform},
+    theme::{Class, Style, Theme, ThemeHandle},
+    widget::Widget,
     Screen,
 };
 
+// Clicking a widget with this switches the screen's theme.
+component!(ThemeSwitch(ThemeHandle));
+
+/// Flips between the dark and light theme once per press on a [`ThemeSwitch`].
+/// `OnClick` can't do it, as it is called on every frame the button is held.
+struct ThemeToggle {
+    dark: bool,
+    down: bool,
+}
+
+impl Extension for ThemeToggle {
+    fn before_render(
+        &mut self,
+        widgets: &[Arc<Widget>],
+        _transform: RawTransform,
+        backend: &dyn Backend,
+    ) {
+        let down = backend.get_mouse_down(MouseButton::Left);
+        let pressed = down && !self.down;
+        self.down = down;
+
+        let Some((x, y)) = backend.get_mouse_pos().filter(|_| pressed) else {
+            return;
+        };
+        let hit = hit_test(widgets, x, y);
+        if let Some(ThemeSwitch(theme)) = hit.iter().find_map(|w| w.get::<ThemeSwitch>()) {
+            self.dark = !self.dark;
+            theme.set(if self.dark { dark() } else { light() });
+        }
+    }
+}
+
 fn main() {
     let window = Window::new(
         "minifb example",
@@ -28,29 +70,62 @@ fn main() {
     let mut app = Screen::new(window);
     app.extension(MouseExtension::new());
     app.extension(VelocityExtension);
+    app.extension(ThemeToggle {
+        dark: true,
+        down: false,
+    });
 
-    milestones(&mut app);
-    // todos(&mut app);
+    // `cargo run -- todos` shows the todo list demo instead.
+    match std::env::args().nth(1).as_deref() {
+        Some("todos") => todos(&mut app),
+        _ => milestones(&mut app),
+    }
 
     app.run().unwrap();
 }
 
+fn dark() -> Theme {
+    Theme::new()
+        .root(Style::new().background(Color::BLACK).color(Color::WHITE))
+        .class(
+            "card",
+            Style::new()
+                .background(Color::rgb(0x1D1D1D))
+                .corner_radius(24),
+        )
+        .class("title", Style::new().size(40.0))
+}
+
+fn light() -> Theme {
+    Theme::new()
+        .root(
+            Style::new()
+                .background(Color::rgb(0xF2F2F2))
+                .color(Color::rgb(0x1D1D1D)),
+        )
+        .class(
+            "card",
+            Style::new()
+                .background(Color::WHITE)
+                .corner_radius(24)
+                .box_shadow(BoxShadow::new(0, 4, 16, Color::rgba(0, 0, 0, 0x30))),
+        )
+        .class("title", Style::new().size(40.0))
+}
+
 fn milestones(app: &mut Screen) {
-    app.draw(format!("My Project"))
+    app.set_theme(dark());
+    let theme = app.theme();
+
+    app.draw(Text::new("My Project"))
+        .component(Class("title"))
         .component(Transform::center().top(20));
 
     for i in 0..4 {
-        let mut milestone = Div {
-            style: DivStyle {
-                background_color: 0x1D1D1D,
-                corner_radius: 24,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
+        let mut milestone = Div::default();
 
         milestone
-            .draw(format!("My Milestone"))
+            .draw(Text::new("My Milestone"))
             .component(Transform::center());
 
         app.draw(milestone)
@@ -58,6 +133,8 @@ fn milestones(app: &mut Screen) {
                 osgui::style::Position::Center,
                 osgui::style::Position::Const(145 + ((194 + 30) * i)),
             ))
+            .component(Class("card title"))
+            .component(ThemeSwitch(theme.clone()))
             .component(OnClick(|_| println!("Clicked!")))
             .component(Velocity(300, 0));
     }
@@ -66,10 +143,7 @@ fn milestones(app: &mut Screen) {
 fn todos(app: &mut Screen) {
     for row in 0..10 {
         let mut task = Div {
-            style: DivStyle {
-                background_color: 0x191919,
-                corner_radius: 13,
-            },
+            style: DivStyle::new().background(0x191919).corner_radius(13),
             ..Default::default()
         };
 
@@ -0,0 +1,106 @@
+use crate::color::Color;
+
+/// How a shape or text is filled: a solid [`Color`] or a gradient.
+///
+/// Anything that converts into a [`Color`] also converts into a solid paint.
+/// Gradients span the box of whatever they fill and are dithered so smooth
+/// ramps don't band.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Color),
+    /// Colors change along a line at `angle` degrees, clockwise from pointing
+    /// up like CSS, so `90.0` runs left to right.
+    Linear {
+        angle: f32,
+        stops: Vec<(f32, Color)>,
+    },
+    /// Colors change outward from `center`, given as a fraction of the box,
+    /// reaching the last stop at the farthest corner.
+    Radial {
+        center: (f32, f32),
+        stops: Vec<(f32, Color)>,
+    },
+}
+
+impl Paint {
+    /// A linear gradient; each stop is an offset in `0.0..=1.0` and a color.
+    pub fn linear<C: Into<Color>>(angle: f32, stops: impl IntoIterator<Item = (f32, C)>) -> Self {
+        Paint::Linear {
+            angle,
+            stops: collect_stops(stops),
+        }
+    }
+
+    /// A radial gradient from the middle of the box.
+    pub fn radial<C: Into<Color>>(stops: impl IntoIterator<Item = (f32, C)>) -> Self {
+        Paint::Radial {
+            center: (0.5, 0.5),
+            stops: collect_stops(stops),
+        }
+    }
+
+    /// Moves the center of a radial gradient; other paints are unchanged.
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        if let Paint::Radial { center, .. } = &mut self {
+            *center = (x, y);
+        }
+        self
+    }
+
+    /// The color for a solid paint.
+    pub fn as_solid(&self) -> Option<Color> {
+        match self {
+            Paint::Solid(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    /// Whether nothing would be drawn with this paint.
+    pub fn is_transparent(&self) -> bool {
+        match self {
+            Paint::Solid(color) => color.is_transparent(),
+            Paint::Linear { stops, .. } | Paint::Radial { stops, .. } => {
+                stops.iter().all(|(_, c)| c.is_transparent())
+            }
+        }
+    }
+}
+
+fn collect_stops<C: Into<Color>>(stops: impl IntoIterator<Item = (f32, C)>) -> Vec<(f32, Color)> {
+    let mut stops: Vec<(f32, Color)> = stops
+        .into_iter()
+        .map(|(offset, color)| (offset.clamp(0.0, 1.0), color.into()))
+        .collect();
+    stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+    stops
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Paint::Solid(Color::TRANSPARENT)
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Solid(color)
+    }
+}
+
+impl From<u32> for Paint {
+    fn from(rgb: u32) -> Self {
+        Paint::Solid(rgb.into())
+    }
+}
+
+impl From<(u8, u8, u8)> for Paint {
+    fn from(rgb: (u8, u8, u8)) -> Self {
+        Paint::Solid(rgb.into())
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Paint {
+    fn from(rgba: (u8, u8, u8, u8)) -> Self {
+        Paint::Solid(rgba.into())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    render::{
+        paint::mix,
+        surface::{box_distance, Surface},
+    },
+    style::{Border, Corners},
+};
+
+/// Draws `border` along the inside of the `width`×`height` box at `(x, y)` in
+/// buffer space. Sides of different widths meet along the line between the
+/// outer and inner corners, and the inner corners become ellipses.
+pub(crate) fn draw(
+    surface: &mut Surface,
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+    corners: Corners,
+    border: &Border,
+) {
+    let (w, h) = (width as f32, height as f32);
+    let sides = border.width;
+    let (top, right, bottom, left) = (
+        sides.top as f32,
+        sides.right as f32,
+        sides.bottom as f32,
+        sides.left as f32,
+    );
+    let outer = corners.to_array().map(|r| (r as f32, r as f32));
+    let inner = [
+        (outer[0].0 - left, outer[0].1 - top),
+        (outer[1].0 - right, outer[1].1 - top),
+        (outer[2].0 - right, outer[2].1 - bottom),
+        (outer[3].0 - left, outer[3].1 - bottom),
+    ]
+    .map(|(rx, ry)| (rx.max(0.0), ry.max(0.0)));
+    let (inner_w, inner_h) = (w - left - right, h - top - bottom);
+
+    // Rows between the inner corners only need the left and right strips.
+    let straight_top = (top + inner[0].1.max(inner[1].1)).ceil() as i32;
+    let straight_bottom = (h - bottom - inner[2].1.max(inner[3].1)).floor() as i32;
+    let (skip_x0, skip_x1) = (left.ceil() as i32 + 1, (w - right).floor() as i32 - 1);
+
+    let (bx0, by0, bx1, by1) = surface.bounds();
+    for ly in (by0 - y).max(0)..(height as i32).min(by1 - y) {
+        let (lx0, lx1) = ((bx0 - x).max(0), (width as i32).min(bx1 - x));
+        let (gap0, gap1) = if ly >= straight_top && ly < straight_bottom {
+            (skip_x0.clamp(lx0, lx1), skip_x1.clamp(lx0, lx1))
+        } else {
+            (lx1, lx1)
+        };
+        for lx in (lx0..gap0).chain(gap1.max(gap0)..lx1) {
+            let (px, py) = (lx as f32 + 0.5, ly as f32 + 0.5);
+            let outside = (0.5 - box_distance(px, py, w, h, outer)).clamp(0.0, 1.0);
+            if outside <= 0.0 {
+                continue;
+            }
+            let hole = if inner_w > 0.0 && inner_h > 0.0 {
+                (0.5 - box_distance(px - left, py - top, inner_w, inner_h, inner)).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let coverage = outside * (1.0 - hole);
+            if coverage <= 0.0 {
+                continue;
+            }
+
+            // The side whose edge is nearest relative to its width takes the
+            // pixel, shading into the next nearest across the seam between them.
+            let relative = |d: f32, side: f32| if side > 0.0 { d / side } else { f32::MAX };
+            let mut candidates = [
+                (relative(py, top), top, border.color.top),
+                (relative(w - px, right), right, border.color.right),
+                (relative(h - py, bottom), bottom, border.color.bottom),
+                (relative(px, left), left, border.color.left),
+            ];
+            candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let [(rel_a, side_a, color_a), (rel_b, side_b, color_b), ..] = candidates;
+            let color = if color_a == color_b || rel_b == f32::MAX {
+                color_a
+            } else {
+                // Pixels from the seam, which runs where both relative distances match.
+                let seam = (rel_b - rel_a) * side_a * side_b / side_a.hypot(side_b);
+                mix(color_a, color_b, (0.5 - seam).clamp(0.0, 0.5), 0.0)
+            };
+            surface.blend(x + lx, y + ly, color, coverage);
+        }
+    }
+}
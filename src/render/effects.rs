@@ -0,0 +1,299 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    color::Color,
+    render::surface::{box_distance, corner_coverage, Surface},
+    style::{BoxShadow, Corners, Sides},
+};
+
+/// Cached masks are dropped all at once when there are more than this many.
+const MASK_CACHE_LIMIT: usize = 64;
+
+/// Backdrop blurs wider than this standard deviation run on a shrunk copy,
+/// blurring it by at most about this much.
+const BLUR_DOWNSAMPLE_SIGMA: usize = 3;
+
+/// The blurred coverage of a rounded rectangle, with `pad` pixels of fade on every side.
+struct Mask {
+    pad: usize,
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct MaskKey {
+    width: usize,
+    height: usize,
+    radii: [usize; 4],
+    blur: usize,
+}
+
+static MASKS: Lazy<Mutex<HashMap<MaskKey, Arc<Mask>>>> = Lazy::new(Default::default);
+
+/// Draws `shadow` for the `width`×`height` box at `(x, y)` in buffer space.
+/// Outer shadows only show outside the box and inset ones only inside it.
+pub(crate) fn draw_shadow(
+    surface: &mut Surface,
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+    corners: Corners,
+    shadow: &BoxShadow,
+) {
+    if shadow.color.is_transparent() {
+        return;
+    }
+
+    // Inset shadows are cast by the edge around a shape that shrinks with the spread.
+    let spread = if shadow.inset {
+        -shadow.spread
+    } else {
+        shadow.spread
+    };
+    let grow = |size: usize| (size as i64 + 2 * spread as i64).max(0) as usize;
+    let (shape_w, shape_h) = (grow(width), grow(height));
+    let radii = if spread >= 0 {
+        corners.outset(spread as usize)
+    } else {
+        corners.inset(Sides::all(spread.unsigned_abs() as usize))
+    };
+    let mask = mask(shape_w, shape_h, radii, shadow.blur);
+    let mask_x = x + shadow.offset.0 - spread - mask.pad as i32;
+    let mask_y = y + shadow.offset.1 - spread - mask.pad as i32;
+    let mask_at = |px: i32, py: i32| {
+        let (mx, my) = (px - mask_x, py - mask_y);
+        if mx < 0 || my < 0 || mx >= mask.width as i32 || my >= mask.height as i32 {
+            0.0
+        } else {
+            mask.values[my as usize * mask.width + mx as usize]
+        }
+    };
+
+    let (bx0, by0, bx1, by1) = surface.bounds();
+    let (x0, y0, x1, y1) = if shadow.inset {
+        (x, y, x + width as i32, y + height as i32)
+    } else {
+        (
+            mask_x,
+            mask_y,
+            mask_x + mask.width as i32,
+            mask_y + mask.height as i32,
+        )
+    };
+
+    for py in y0.max(by0)..y1.min(by1) {
+        for px in x0.max(bx0)..x1.min(bx1) {
+            let shade = if shadow.inset {
+                1.0 - mask_at(px, py)
+            } else {
+                mask_at(px, py)
+            };
+            if shade <= 0.0 {
+                continue;
+            }
+            let inside = corner_coverage(px - x, py - y, width, height, corners);
+            let coverage = if shadow.inset {
+                inside * shade
+            } else {
+                (1.0 - inside) * shade
+            };
+            if coverage > 0.0 {
+                surface.blend(px, py, shadow.color, coverage);
+            }
+        }
+    }
+}
+
+/// Blurs what is already drawn under the `width`×`height` box at `(x, y)` in
+/// buffer space, with a Gaussian of standard deviation `radius` like CSS's `blur()`.
+pub(crate) fn backdrop_blur(
+    surface: &mut Surface,
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+    corners: Corners,
+    radius: usize,
+) {
+    let (bx0, by0, bx1, by1) = surface.bounds();
+    let (x0, y0) = (x.max(bx0), y.max(by0));
+    let (x1, y1) = ((x + width as i32).min(bx1), (y + height as i32).min(by1));
+    if radius == 0 || x0 >= x1 || y0 >= y1 {
+        return;
+    }
+
+    // Read a margin around the box so its edges blur with their real surroundings.
+    let r = box_radius(radius as f32);
+    let margin = 3 * r as i32;
+    let (buf_w, buf_h) = surface.size();
+    let (rx0, ry0) = ((x0 - margin).max(0), (y0 - margin).max(0));
+    let (rx1, ry1) = (
+        (x1 + margin).min(buf_w as i32),
+        (y1 + margin).min(buf_h as i32),
+    );
+    let (rw, rh) = ((rx1 - rx0) as usize, (ry1 - ry0) as usize);
+
+    // A wide blur hides detail anyway, so blur a copy shrunk by averaging
+    // `scale`×`scale` blocks and sample it back up.
+    let scale = (radius / BLUR_DOWNSAMPLE_SIGMA).max(1);
+    let (sw, sh) = (rw.div_ceil(scale), rh.div_ceil(scale));
+    let mut planes = [vec![0.0; sw * sh], vec![0.0; sw * sh], vec![0.0; sw * sh]];
+    for row in 0..rh {
+        let start = row / scale * sw;
+        for block in 0..sw {
+            let (c0, c1) = (block * scale, ((block + 1) * scale).min(rw));
+            let i = start + block;
+            for col in c0..c1 {
+                let p = surface.pixel(rx0 as usize + col, ry0 as usize + row);
+                planes[0][i] += ((p >> 16) & 0xFF) as f32;
+                planes[1][i] += ((p >> 8) & 0xFF) as f32;
+                planes[2][i] += (p & 0xFF) as f32;
+            }
+        }
+    }
+    for (i, count) in block_counts(rw, rh, scale).enumerate() {
+        for plane in &mut planes {
+            plane[i] /= count;
+        }
+    }
+    for plane in &mut planes {
+        box_blur(plane, sw, sh, box_radius(radius as f32 / scale as f32));
+    }
+
+    let sample = |pos: i32, len: usize| {
+        let s = ((pos as f32 + 0.5) / scale as f32 - 0.5).clamp(0.0, (len - 1) as f32);
+        let i = (s as usize).min(len.saturating_sub(2));
+        (i, (s - i as f32).min(1.0))
+    };
+    let columns: Vec<_> = (x0..x1).map(|px| sample(px - rx0, sw)).collect();
+    for py in y0..y1 {
+        let (sy, fy) = sample(py - ry0, sh);
+        let sy1 = (sy + 1).min(sh - 1);
+        for (px, &(sx, fx)) in (x0..x1).zip(&columns) {
+            let coverage = corner_coverage(px - x, py - y, width, height, corners);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let sx1 = (sx + 1).min(sw - 1);
+            let channel = |plane: &Vec<f32>| {
+                let top = plane[sy * sw + sx] * (1.0 - fx) + plane[sy * sw + sx1] * fx;
+                let bottom = plane[sy1 * sw + sx] * (1.0 - fx) + plane[sy1 * sw + sx1] * fx;
+                (top * (1.0 - fy) + bottom * fy).round().clamp(0.0, 255.0) as u8
+            };
+            let color = Color::rgba(
+                channel(&planes[0]),
+                channel(&planes[1]),
+                channel(&planes[2]),
+                0xFF,
+            );
+            surface.blend(px, py, color, coverage);
+        }
+    }
+}
+
+/// How many pixels each block of a `width`×`height` plane shrunk by `scale`
+/// averages, row by row; blocks on the right and bottom edges may be partial.
+fn block_counts(width: usize, height: usize, scale: usize) -> impl Iterator<Item = f32> {
+    let span = move |i: usize, len: usize| (len - i * scale).min(scale);
+    (0..height.div_ceil(scale)).flat_map(move |row| {
+        (0..width.div_ceil(scale)).map(move |col| (span(row, height) * span(col, width)) as f32)
+    })
+}
+
+/// The blurred mask of a `width`×`height` rounded rectangle, built on first use.
+fn mask(width: usize, height: usize, corners: Corners, blur: usize) -> Arc<Mask> {
+    let key = MaskKey {
+        width,
+        height,
+        radii: corners.to_array(),
+        blur,
+    };
+    if let Some(mask) = MASKS.lock().unwrap().get(&key) {
+        return mask.clone();
+    }
+
+    // A CSS blur radius is twice the Gaussian's standard deviation.
+    let r = box_radius(blur as f32 / 2.0);
+    let pad = 3 * r;
+    let (mw, mh) = (width + pad * 2, height + pad * 2);
+    let radii = corners.to_array().map(|r| (r as f32, r as f32));
+    let mut values = vec![0.0; mw * mh];
+    if width > 0 && height > 0 {
+        for my in 0..mh {
+            for mx in 0..mw {
+                let px = mx as f32 - pad as f32 + 0.5;
+                let py = my as f32 - pad as f32 + 0.5;
+                let d = box_distance(px, py, width as f32, height as f32, radii);
+                values[my * mw + mx] = (0.5 - d).clamp(0.0, 1.0);
+            }
+        }
+        box_blur(&mut values, mw, mh, r);
+    }
+
+    let mask = Arc::new(Mask {
+        pad,
+        width: mw,
+        height: mh,
+        values,
+    });
+    let mut masks = MASKS.lock().unwrap();
+    if masks.len() >= MASK_CACHE_LIMIT {
+        masks.clear();
+    }
+    masks.insert(key, mask.clone());
+    mask
+}
+
+/// Radius of the box filter that, applied three times, approximates a
+/// Gaussian with standard deviation `sigma`.
+fn box_radius(sigma: f32) -> usize {
+    (((4.0 * sigma * sigma + 1.0).sqrt() - 1.0) / 2.0).round() as usize
+}
+
+/// Three separable box blur passes over a `width`×`height` plane, repeating
+/// the edge pixels outwards.
+fn box_blur(plane: &mut [f32], width: usize, height: usize, radius: usize) {
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+    let mut line = Vec::new();
+    let mut out = Vec::new();
+    for _ in 0..3 {
+        for row in plane.chunks_mut(width) {
+            line.clear();
+            line.extend_from_slice(row);
+            blur_line(&line, &mut out, radius);
+            row.copy_from_slice(&out);
+        }
+        for col in 0..width {
+            line.clear();
+            line.extend((0..height).map(|row| plane[row * width + col]));
+            blur_line(&line, &mut out, radius);
+            for (row, v) in out.iter().enumerate() {
+                plane[row * width + col] = *v;
+            }
+        }
+    }
+}
+
+/// A running-sum box blur of `src` into `dst`.
+fn blur_line(src: &[f32], dst: &mut Vec<f32>, radius: usize) {
+    let n = src.len() as isize;
+    let at = |i: isize| src[i.clamp(0, n - 1) as usize];
+    let r = radius as isize;
+    let scale = 1.0 / (2 * radius + 1) as f32;
+
+    dst.clear();
+    let mut sum: f32 = (-r..=r).map(at).sum();
+    for i in 0..n {
+        dst.push(sum * scale);
+        sum += at(i + r + 1) - at(i - r);
+    }
+}
@@ -0,0 +1,350 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::Path,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use once_cell::sync::Lazy;
+use rusttype::{point, Font, PositionedGlyph, Scale, VMetrics};
+
+use crate::{
+    render::default_font,
+    style::{FontWeight, TextStyle},
+};
+
+/// Family name of the bundled font, always registered and last in every chain.
+pub const DEFAULT_FAMILY: &str = "DejaVu Sans";
+
+/// When more layouts than this are cached, the least recently used quarter is dropped.
+const LAYOUT_CACHE_LIMIT: usize = 4096;
+
+struct Face {
+    /// Unique for the lifetime of the app, so caches can tell replaced faces apart.
+    id: usize,
+    family: String,
+    weight: FontWeight,
+    italic: bool,
+    font: Font<'static>,
+}
+
+struct Registry {
+    faces: Vec<Face>,
+    default_family: String,
+    fallback: Vec<String>,
+    /// Fallback families of single families, by lowercase name, tried before `fallback`.
+    family_fallback: HashMap<String, Vec<String>>,
+    next_id: usize,
+}
+
+/// Resolved chains per style family, then per weight and italic, so lookups
+/// don't allocate. Cleared whenever the registry changes.
+type ChainCache = HashMap<Option<String>, HashMap<(FontWeight, bool), Arc<[ResolvedFont]>>>;
+
+/// Everything in a [`TextStyle`] that changes the layout.
+#[derive(PartialEq, Eq, Hash)]
+struct LayoutKey {
+    family: Option<String>,
+    weight: FontWeight,
+    italic: bool,
+    size: u32,
+    letter_spacing: u32,
+}
+
+static REGISTRY: Lazy<RwLock<Registry>> = Lazy::new(|| {
+    RwLock::new(Registry {
+        faces: vec![Face {
+            id: 0,
+            family: DEFAULT_FAMILY.to_string(),
+            weight: FontWeight::NORMAL,
+            italic: false,
+            font: default_font().clone(),
+        }],
+        default_family: DEFAULT_FAMILY.to_string(),
+        fallback: Vec::new(),
+        family_fallback: HashMap::new(),
+        next_id: 1,
+    })
+});
+
+/// Layouts per style, then per text, so lookups don't allocate. Every entry
+/// keeps the tick it was last used at, so text that is being edited ages out
+/// without evicting the text that is drawn every frame.
+#[derive(Default)]
+struct LayoutCache {
+    runs: HashMap<LayoutKey, HashMap<String, (Arc<GlyphRun>, u64)>>,
+    len: usize,
+    tick: u64,
+}
+
+impl LayoutCache {
+    fn get(&mut self, key: &LayoutKey, text: &str) -> Option<Arc<GlyphRun>> {
+        self.tick += 1;
+        let (run, used) = self.runs.get_mut(key)?.get_mut(text)?;
+        *used = self.tick;
+        Some(run.clone())
+    }
+
+    fn insert(&mut self, key: LayoutKey, text: &str, run: Arc<GlyphRun>) {
+        if self.len >= LAYOUT_CACHE_LIMIT {
+            self.evict(LAYOUT_CACHE_LIMIT / 4);
+        }
+        let runs = self.runs.entry(key).or_default();
+        if runs.insert(text.to_string(), (run, self.tick)).is_none() {
+            self.len += 1;
+        }
+    }
+
+    /// Drops the `count` least recently used layouts.
+    fn evict(&mut self, count: usize) {
+        let mut ticks: Vec<u64> = self
+            .runs
+            .values()
+            .flat_map(|runs| runs.values().map(|(_, used)| *used))
+            .collect();
+        if count == 0 || ticks.is_empty() {
+            return;
+        }
+        let nth = count.min(ticks.len()) - 1;
+        let cutoff = *ticks.select_nth_unstable(nth).1;
+        for runs in self.runs.values_mut() {
+            runs.retain(|_, (_, used)| *used > cutoff);
+        }
+        self.runs.retain(|_, runs| !runs.is_empty());
+        self.len = self.runs.values().map(HashMap::len).sum();
+    }
+
+    fn clear(&mut self) {
+        self.runs.clear();
+        self.len = 0;
+    }
+}
+
+static LAYOUTS: Lazy<Mutex<LayoutCache>> = Lazy::new(Default::default);
+static CHAINS: Lazy<Mutex<ChainCache>> = Lazy::new(Default::default);
+
+/// Drops everything resolved from the registry before it changed.
+fn invalidate() {
+    CHAINS.lock().unwrap().clear();
+    LAYOUTS.lock().unwrap().clear();
+}
+
+/// A face picked for a [`TextStyle`], and what has to be synthesized because it lacks it.
+#[derive(Clone)]
+pub struct ResolvedFont {
+    pub id: usize,
+    pub font: Font<'static>,
+    pub fake_bold: bool,
+    pub fake_italic: bool,
+}
+
+/// One line of text laid out over a font chain, with the baseline starting at `(0, 0)`.
+pub struct GlyphRun {
+    pub fonts: Arc<[ResolvedFont]>,
+    /// Each glyph with the index of the font in `fonts` it comes from.
+    pub glyphs: Vec<(usize, PositionedGlyph<'static>)>,
+    /// Metrics of the first font in the chain.
+    pub v_metrics: VMetrics,
+    /// Advance from the origin to the end of the last glyph, including letter spacing.
+    pub width: f32,
+}
+
+/// Registers a TrueType or OpenType font, replacing any face with the same
+/// family, weight and style.
+pub fn register_font(
+    family: &str,
+    weight: FontWeight,
+    italic: bool,
+    data: Vec<u8>,
+) -> io::Result<()> {
+    let font = Font::try_from_vec(data).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a TrueType or OpenType font",
+        )
+    })?;
+
+    let mut registry = REGISTRY.write().unwrap();
+    registry.faces.retain(|f| {
+        !(f.family.eq_ignore_ascii_case(family) && f.weight == weight && f.italic == italic)
+    });
+    let id = registry.next_id;
+    registry.next_id += 1;
+    registry.faces.push(Face {
+        id,
+        family: family.to_string(),
+        weight,
+        italic,
+        font,
+    });
+    drop(registry);
+    invalidate();
+    Ok(())
+}
+
+/// Reads a font file and registers it with [`register_font`].
+pub fn load_font(
+    family: &str,
+    weight: FontWeight,
+    italic: bool,
+    path: impl AsRef<Path>,
+) -> io::Result<()> {
+    register_font(family, weight, italic, std::fs::read(path)?)
+}
+
+pub fn has_family(family: &str) -> bool {
+    REGISTRY.read().unwrap().has(family)
+}
+
+/// The family used for styles without one, or with one that isn't registered.
+pub fn set_default_family(family: &str) {
+    REGISTRY.write().unwrap().default_family = family.to_string();
+    invalidate();
+}
+
+/// Families tried, in order, for chars the style's own font has no glyph for.
+pub fn set_fallback(families: &[&str]) {
+    REGISTRY.write().unwrap().fallback = families.iter().map(|f| f.to_string()).collect();
+    invalidate();
+}
+
+/// Families tried, in order, for chars `family` has no glyph for, before the
+/// ones from [`set_fallback`]. An empty list removes them.
+pub fn set_family_fallback(family: &str, families: &[&str]) {
+    let families = families.iter().map(|f| f.to_string()).collect();
+    REGISTRY
+        .write()
+        .unwrap()
+        .family_fallback
+        .insert(family.to_lowercase(), families);
+    invalidate();
+}
+
+/// The fonts tried in order for every char of text in `style`: its family,
+/// that family's fallbacks, the global ones and then [`DEFAULT_FAMILY`].
+/// Resolved once per family, weight and italic.
+pub fn font_chain(style: &TextStyle) -> Arc<[ResolvedFont]> {
+    let key = (style.weight, style.italic);
+    if let Some(chain) = CHAINS
+        .lock()
+        .unwrap()
+        .get(&style.family)
+        .and_then(|chains| chains.get(&key))
+    {
+        return chain.clone();
+    }
+
+    let chain: Arc<[ResolvedFont]> = resolve_chain(style).into();
+    CHAINS
+        .lock()
+        .unwrap()
+        .entry(style.family.clone())
+        .or_default()
+        .insert(key, chain.clone());
+    chain
+}
+
+fn resolve_chain(style: &TextStyle) -> Vec<ResolvedFont> {
+    let registry = REGISTRY.read().unwrap();
+    let primary = style
+        .family
+        .as_deref()
+        .filter(|f| registry.has(f))
+        .unwrap_or(&registry.default_family);
+    let own = registry
+        .family_fallback
+        .get(&primary.to_lowercase())
+        .into_iter()
+        .flatten();
+
+    let mut chain: Vec<ResolvedFont> = Vec::new();
+    let families = std::iter::once(primary)
+        .chain(own.map(String::as_str))
+        .chain(registry.fallback.iter().map(String::as_str))
+        .chain(std::iter::once(DEFAULT_FAMILY));
+    for family in families {
+        let Some(face) = registry.best(family, style.weight, style.italic) else {
+            continue;
+        };
+        if chain.iter().any(|f| f.id == face.id) {
+            continue;
+        }
+        chain.push(ResolvedFont {
+            id: face.id,
+            font: face.font.clone(),
+            fake_bold: style.weight >= FontWeight::SEMI_BOLD && face.weight < FontWeight::SEMI_BOLD,
+            fake_italic: style.italic && !face.italic,
+        });
+    }
+    chain
+}
+
+/// Lays out one line, taking each glyph from the first font in the chain
+/// that has it. Layouts are cached per style and text.
+pub fn layout(text: &str, style: &TextStyle) -> Arc<GlyphRun> {
+    let key = LayoutKey {
+        family: style.family.clone(),
+        weight: style.weight,
+        italic: style.italic,
+        size: style.size.to_bits(),
+        letter_spacing: style.letter_spacing.to_bits(),
+    };
+
+    if let Some(run) = LAYOUTS.lock().unwrap().get(&key, text) {
+        return run;
+    }
+
+    let run = Arc::new(build(text, style));
+    LAYOUTS.lock().unwrap().insert(key, text, run.clone());
+    run
+}
+
+fn build(text: &str, style: &TextStyle) -> GlyphRun {
+    let fonts = font_chain(style);
+    let scale = Scale::uniform(style.size);
+    let mut glyphs = Vec::with_capacity(text.len());
+    let mut x = 0.0;
+    let mut prev = None;
+
+    for c in text.chars() {
+        let index = fonts
+            .iter()
+            .position(|f| f.font.glyph(c).id().0 != 0)
+            .unwrap_or(0);
+        let font = &fonts[index].font;
+        let glyph = font.glyph(c).scaled(scale);
+        if let Some((prev_index, prev_id)) = prev {
+            if prev_index == index {
+                x += font.pair_kerning(scale, prev_id, glyph.id());
+            }
+        }
+        prev = Some((index, glyph.id()));
+
+        let advance = glyph.h_metrics().advance_width;
+        glyphs.push((index, glyph.positioned(point(x, 0.0))));
+        x += advance + style.letter_spacing;
+    }
+
+    GlyphRun {
+        v_metrics: fonts[0].font.v_metrics(scale),
+        fonts,
+        glyphs,
+        width: x,
+    }
+}
+
+impl Registry {
+    fn has(&self, family: &str) -> bool {
+        self.faces
+            .iter()
+            .any(|f| f.family.eq_ignore_ascii_case(family))
+    }
+
+    /// The face of `family` matching the style first, then closest in weight.
+    fn best(&self, family: &str, weight: FontWeight, italic: bool) -> Option<&Face> {
+        self.faces
+            .iter()
+            .filter(|f| f.family.eq_ignore_ascii_case(family))
+            .min_by_key(|f| (f.italic != italic, f.weight.0.abs_diff(weight.0)))
+    }
+}
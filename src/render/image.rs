@@ -0,0 +1,79 @@
+use crate::{
+    color::Color,
+    image::{Filter, ImageData},
+    render::surface::Surface,
+};
+
+/// Draws `image` scaled to the `width` x `height` rectangle at `(x, y)` in buffer space.
+pub(crate) fn draw(
+    surface: &mut Surface,
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+    image: &ImageData,
+    filter: Filter,
+) {
+    if width == 0 || height == 0 || image.width == 0 || image.height == 0 {
+        return;
+    }
+
+    let (bx0, by0, bx1, by1) = surface.bounds();
+    let scale_x = image.width as f32 / width as f32;
+    let scale_y = image.height as f32 / height as f32;
+
+    for dy in (by0 - y).max(0)..(height as i32).min(by1 - y) {
+        // Source coordinates of the pixel center.
+        let sy = (dy as f32 + 0.5) * scale_y - 0.5;
+        for dx in (bx0 - x).max(0)..(width as i32).min(bx1 - x) {
+            let sx = (dx as f32 + 0.5) * scale_x - 0.5;
+            let pixel = match filter {
+                Filter::Nearest => image.get_pixel(
+                    (sx.round().max(0.0) as usize).min(image.width - 1),
+                    (sy.round().max(0.0) as usize).min(image.height - 1),
+                ),
+                Filter::Bilinear => bilinear(image, sx, sy),
+            };
+            let color = Color(pixel);
+            if !color.is_transparent() {
+                surface.blend(x + dx, y + dy, color, 1.0);
+            }
+        }
+    }
+}
+
+/// Samples between the four nearest pixels, weighting colors by alpha so
+/// transparent neighbours don't darken edges.
+fn bilinear(image: &ImageData, sx: f32, sy: f32) -> u32 {
+    let clamp_x = |v: f32| (v.max(0.0) as usize).min(image.width - 1);
+    let clamp_y = |v: f32| (v.max(0.0) as usize).min(image.height - 1);
+    let (x0, y0) = (sx.floor(), sy.floor());
+    let (fx, fy) = (sx - x0, sy - y0);
+    let (xa, xb) = (clamp_x(x0), clamp_x(x0 + 1.0));
+    let (ya, yb) = (clamp_y(y0), clamp_y(y0 + 1.0));
+
+    let samples = [
+        (image.get_pixel(xa, ya), (1.0 - fx) * (1.0 - fy)),
+        (image.get_pixel(xb, ya), fx * (1.0 - fy)),
+        (image.get_pixel(xa, yb), (1.0 - fx) * fy),
+        (image.get_pixel(xb, yb), fx * fy),
+    ];
+
+    let mut sum = [0.0f32; 4];
+    for (p, w) in samples {
+        let a = (p >> 24) as f32 * w;
+        sum[0] += a;
+        sum[1] += ((p >> 16) & 0xFF) as f32 * a;
+        sum[2] += ((p >> 8) & 0xFF) as f32 * a;
+        sum[3] += (p & 0xFF) as f32 * a;
+    }
+    if sum[0] <= 0.0 {
+        return 0;
+    }
+
+    let channel = |v: f32| (v / sum[0]).round().clamp(0.0, 255.0) as u32;
+    ((sum[0].round().min(255.0) as u32) << 24)
+        | (channel(sum[1]) << 16)
+        | (channel(sum[2]) << 8)
+        | channel(sum[3])
+}
@@ -0,0 +1,718 @@
+mod border;
+mod effects;
+pub mod font;
+mod image;
+mod paint;
+pub mod path;
+mod surface;
+mod text;
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use rusttype::Font;
+
+pub use surface::Clip;
+#[cfg(any(test, feature = "testing"))]
+pub(crate) use text::glyphs_rasterized;
+
+use crate::{
+    component,
+    image::{Filter, ImageData},
+    paint::Paint,
+    render::{
+        paint::Shader,
+        path::{FillRule, Path},
+        surface::{corner_coverage, Surface},
+    },
+    style::{Border, BoxShadow, Corners, Outline, RawTransform, TextStyle, Transform},
+    theme::{Class, Style, Theme},
+    widget::Widget,
+};
+
+const FONT: &[u8] = include_bytes!("../../assets/DejaVuSans.ttf");
+static FONT_OBJ: Lazy<Font<'static>> = Lazy::new(|| Font::try_from_bytes(FONT).unwrap());
+
+/// The bundled font, registered as [`font::DEFAULT_FAMILY`].
+pub fn default_font() -> &'static Font<'static> {
+    &FONT_OBJ
+}
+
+/// The main font face text in `style` is drawn with; its metrics set the line height.
+pub fn font_for(style: &TextStyle) -> Font<'static> {
+    font::font_chain(style)[0].font.clone()
+}
+
+component!(
+    /// Where a widget was drawn in the last frame, in screen pixels. Set on
+    /// every widget whose scope is drawn, for hit testing.
+    Layout {
+        pub x: i32,
+        pub y: i32,
+        pub width: usize,
+        pub height: usize,
+        /// The part left visible by the clips around it, as `(x0, y0, x1, y1)`.
+        pub visible: (i32, i32, i32, i32),
+    }
+);
+
+impl Layout {
+    /// Whether `(x, y)` is over the visible part of the widget.
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let (x0, y0, x1, y1) = self.visible;
+        x >= x0 as f32 && y >= y0 as f32 && x < x1 as f32 && y < y1 as f32
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RenderMethod {
+    Text(String, i32, i32, TextStyle),
+    Rectangle(i32, i32, usize, usize, Paint),
+    RoundedRectangle(i32, i32, usize, usize, Corners, Paint),
+    /// A border along the inside of a rounded rectangle.
+    Border(i32, i32, usize, usize, Corners, Border),
+    /// A shadow cast by a rounded rectangle.
+    BoxShadow(i32, i32, usize, usize, Corners, BoxShadow),
+    /// Blurs what is already drawn under a rounded rectangle by the given radius.
+    BackdropBlur(i32, i32, usize, usize, Corners, usize),
+    /// An image scaled to the given rectangle.
+    Image(i32, i32, usize, usize, Arc<ImageData>, Filter),
+    FillPath(Path, FillRule, Paint),
+    /// Outlines a path with lines of the given width.
+    StrokePath(Path, f32, Paint),
+    /// Restricts later methods to a rounded rectangle until the matching `PopClip`.
+    PushClip(i32, i32, usize, usize, Corners),
+    PopClip,
+    Merge(Box<RenderScope>),
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderScope {
+    transform: RawTransform,
+    render_stack: Vec<RenderMethod>,
+    parent_width: usize,
+    parent_height: usize,
+    max_size: (usize, usize),
+    buffer: Vec<u32>,
+    theme: Option<Arc<Theme>>,
+    /// The combined style of the rendering widget's classes.
+    class: Style,
+    /// The text style the rendering widget inherits, after its classes.
+    text: TextStyle,
+    /// The widget rendering into the scope, given a [`Layout`] when it is drawn.
+    widget: Option<Arc<Widget>>,
+}
+
+impl RenderScope {
+    pub fn new(w: usize, h: usize) -> Self {
+        Self {
+            buffer: vec![0; w * h],
+            ..Self::child(w, h)
+        }
+    }
+
+    /// A scope that is only ever merged into another one, so it skips
+    /// allocating a pixel buffer of its own.
+    pub fn child(w: usize, h: usize) -> Self {
+        Self {
+            buffer: Vec::new(),
+            render_stack: Vec::new(),
+            parent_width: w,
+            parent_height: h,
+            transform: RawTransform::new(),
+            max_size: (0, 0),
+            theme: None,
+            class: Style::default(),
+            text: TextStyle::default(),
+            widget: None,
+        }
+    }
+
+    /// Styles `widget`, about to render into this scope, with its classes in
+    /// `theme` over the text style it `inherited`.
+    pub(crate) fn apply_theme(
+        &mut self,
+        widget: &Widget,
+        theme: Option<Arc<Theme>>,
+        inherited: &TextStyle,
+    ) {
+        self.class = match (&theme, widget.get::<Class>()) {
+            (Some(theme), Some(Class(names))) => theme.resolve(names),
+            _ => Style::default(),
+        };
+        self.text.clone_from(inherited);
+        self.class.apply_text(&mut self.text);
+        self.theme = theme;
+    }
+
+    /// Makes `widget` the one rendering into this scope.
+    pub(crate) fn set_widget(&mut self, widget: &Arc<Widget>) {
+        self.widget = Some(widget.clone());
+    }
+
+    /// Styles `widget`, about to render into this scope, as a child of `parent`.
+    pub(crate) fn inherit_theme(&mut self, widget: &Widget, parent: &RenderScope) {
+        self.apply_theme(widget, parent.theme.clone(), &parent.text);
+    }
+
+    /// The combined style of the rendering widget's [`Class`]es.
+    pub fn class_style(&self) -> &Style {
+        &self.class
+    }
+
+    /// The text style the rendering widget inherits from its parent and classes.
+    pub fn text_style(&self) -> &TextStyle {
+        &self.text
+    }
+
+    pub fn draw_text(&mut self, x: i32, y: i32, scale: f32, text: &str, color: impl Into<Paint>) {
+        self.draw_styled_text(x, y, text, &TextStyle::new(scale, color));
+    }
+
+    /// Draws one line of text with its top-left corner at `(x, y)`.
+    pub fn draw_styled_text(&mut self, x: i32, y: i32, text: &str, style: &TextStyle) {
+        if style.color.is_transparent() {
+            return;
+        }
+        self.render_stack
+            .push(RenderMethod::Text(text.to_string(), x, y, style.clone()));
+        let run = font::layout(text, style);
+        let h = (run.v_metrics.ascent - run.v_metrics.descent).ceil() as usize;
+        self.transform.width = self.transform.width.max(run.width.ceil() as usize);
+        self.transform.height = self.transform.height.max(h);
+        self.update_size();
+    }
+
+    pub fn draw_rect(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        color: impl Into<Paint>,
+    ) {
+        let color = color.into();
+        if color.is_transparent() {
+            return;
+        }
+        self.render_stack
+            .push(RenderMethod::Rectangle(x, y, width, height, color));
+        self.transform.width = self.transform.width.max(width);
+        self.transform.height = self.transform.height.max(height);
+        self.update_size();
+    }
+
+    pub fn draw_rect_rounded(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        radius: impl Into<Corners>,
+        color: impl Into<Paint>,
+    ) {
+        let color = color.into();
+        let radius = radius.into();
+        if color.is_transparent() {
+            return;
+        }
+        if radius.is_zero() {
+            self.draw_rect(x, y, width, height, color);
+            return;
+        }
+        self.render_stack.push(RenderMethod::RoundedRectangle(
+            x, y, width, height, radius, color,
+        ));
+        self.transform.width = self.transform.width.max(width);
+        self.transform.height = self.transform.height.max(height);
+        self.update_size();
+    }
+
+    /// Draws `border` along the inside of the box, following its rounded corners.
+    pub fn draw_border(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        radius: impl Into<Corners>,
+        border: &Border,
+    ) {
+        if !border.is_visible() {
+            return;
+        }
+        self.render_stack.push(RenderMethod::Border(
+            x,
+            y,
+            width,
+            height,
+            radius.into(),
+            *border,
+        ));
+        self.reserve(width, height);
+    }
+
+    /// Draws `outline` around the outside of the box without growing the reported size.
+    pub fn draw_outline(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        radius: impl Into<Corners>,
+        outline: &Outline,
+    ) {
+        if outline.width == 0 || outline.color.is_transparent() {
+            return;
+        }
+        let grow = outline.offset + outline.width;
+        self.render_stack.push(RenderMethod::Border(
+            x - grow as i32,
+            y - grow as i32,
+            width + grow * 2,
+            height + grow * 2,
+            radius.into().outset(grow),
+            Border::new(outline.width, outline.color),
+        ));
+    }
+
+    /// Draws `shadow` for the box; it doesn't grow the reported size.
+    pub fn draw_box_shadow(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        radius: impl Into<Corners>,
+        shadow: &BoxShadow,
+    ) {
+        if shadow.color.is_transparent() {
+            return;
+        }
+        self.render_stack.push(RenderMethod::BoxShadow(
+            x,
+            y,
+            width,
+            height,
+            radius.into(),
+            *shadow,
+        ));
+    }
+
+    /// Blurs everything drawn before this scope under the box, for frosted
+    /// glass behind a translucent background. `blur` is the Gaussian's standard deviation.
+    pub fn draw_backdrop_blur(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        radius: impl Into<Corners>,
+        blur: usize,
+    ) {
+        if blur == 0 {
+            return;
+        }
+        self.render_stack.push(RenderMethod::BackdropBlur(
+            x,
+            y,
+            width,
+            height,
+            radius.into(),
+            blur,
+        ));
+        self.reserve(width, height);
+    }
+
+    /// Draws `image` scaled to `width` x `height`, blending it by its alpha.
+    pub fn draw_image(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        image: &Arc<ImageData>,
+        filter: Filter,
+    ) {
+        self.render_stack.push(RenderMethod::Image(
+            x,
+            y,
+            width,
+            height,
+            image.clone(),
+            filter,
+        ));
+        self.transform.width = self.transform.width.max(width);
+        self.transform.height = self.transform.height.max(height);
+        self.update_size();
+    }
+
+    /// Grows the reported size without drawing anything.
+    pub fn reserve(&mut self, width: usize, height: usize) {
+        self.transform.width = self.transform.width.max(width);
+        self.transform.height = self.transform.height.max(height);
+        self.update_size();
+    }
+
+    pub fn fill_path(&mut self, path: &Path, rule: FillRule, color: impl Into<Paint>) {
+        let color = color.into();
+        if color.is_transparent() {
+            return;
+        }
+        self.reserve_path(path, 0.0);
+        self.render_stack
+            .push(RenderMethod::FillPath(path.clone(), rule, color));
+    }
+
+    /// Outlines `path` with round joins and flat ends.
+    pub fn stroke_path(&mut self, path: &Path, width: f32, color: impl Into<Paint>) {
+        let color = color.into();
+        if color.is_transparent() || width <= 0.0 {
+            return;
+        }
+        self.reserve_path(path, width / 2.0);
+        self.render_stack
+            .push(RenderMethod::StrokePath(path.clone(), width, color));
+    }
+
+    pub fn draw_line(
+        &mut self,
+        x0: f32,
+        y0: f32,
+        x1: f32,
+        y1: f32,
+        width: f32,
+        color: impl Into<Paint>,
+    ) {
+        self.draw_polyline(&[(x0, y0), (x1, y1)], width, color);
+    }
+
+    pub fn draw_polyline(&mut self, points: &[(f32, f32)], width: f32, color: impl Into<Paint>) {
+        self.stroke_path(&Path::polyline(points, false), width, color);
+    }
+
+    pub fn fill_polygon(&mut self, points: &[(f32, f32)], rule: FillRule, color: impl Into<Paint>) {
+        self.fill_path(&Path::polyline(points, true), rule, color);
+    }
+
+    pub fn fill_circle(&mut self, cx: f32, cy: f32, r: f32, color: impl Into<Paint>) {
+        self.fill_path(&Path::circle(cx, cy, r), FillRule::NonZero, color);
+    }
+
+    pub fn stroke_circle(&mut self, cx: f32, cy: f32, r: f32, width: f32, color: impl Into<Paint>) {
+        self.stroke_path(&Path::circle(cx, cy, r), width, color);
+    }
+
+    pub fn fill_ellipse(&mut self, cx: f32, cy: f32, rx: f32, ry: f32, color: impl Into<Paint>) {
+        self.fill_path(&Path::ellipse(cx, cy, rx, ry), FillRule::NonZero, color);
+    }
+
+    pub fn stroke_ellipse(
+        &mut self,
+        cx: f32,
+        cy: f32,
+        rx: f32,
+        ry: f32,
+        width: f32,
+        color: impl Into<Paint>,
+    ) {
+        self.stroke_path(&Path::ellipse(cx, cy, rx, ry), width, color);
+    }
+
+    /// A circular arc from angle `start` turning by `sweep`, in radians clockwise from the x axis.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_arc(
+        &mut self,
+        cx: f32,
+        cy: f32,
+        r: f32,
+        start: f32,
+        sweep: f32,
+        width: f32,
+        color: impl Into<Paint>,
+    ) {
+        self.stroke_path(&Path::new().arc(cx, cy, r, r, start, sweep), width, color);
+    }
+
+    /// Grows the reported size to the bottom-right corner of `path`, widened by `margin`.
+    fn reserve_path(&mut self, path: &Path, margin: f32) {
+        if let Some((_, _, x1, y1)) = path.bounds() {
+            self.reserve(
+                (x1 + margin).ceil().max(0.0) as usize,
+                (y1 + margin).ceil().max(0.0) as usize,
+            );
+        }
+    }
+
+    /// Clips everything drawn after this call, including merged scopes, to the
+    /// given rectangle with rounded corners. Clips nest until [`Self::pop_clip`].
+    pub fn push_clip(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: usize,
+        height: usize,
+        radius: impl Into<Corners>,
+    ) {
+        self.render_stack
+            .push(RenderMethod::PushClip(x, y, width, height, radius.into()));
+    }
+
+    pub fn pop_clip(&mut self) {
+        self.render_stack.push(RenderMethod::PopClip);
+    }
+
+    pub fn merge(&mut self, scope: RenderScope) {
+        let (w, h) = scope.get_max_size();
+        self.render_stack.push(RenderMethod::Merge(Box::new(scope)));
+        self.transform.width = self.transform.width.max(w);
+        self.transform.height = self.transform.height.max(h);
+        self.update_size();
+    }
+}
+
+// After draw
+impl RenderScope {
+    pub fn draw(&mut self) {
+        let stride = self.parent_width;
+        let mut tmp = std::mem::take(&mut self.buffer);
+        self.draw_buf(&mut tmp, stride);
+        self.buffer = tmp;
+    }
+
+    pub fn draw_buf(&mut self, buf: &mut [u32], stride: usize) {
+        self.draw_surface(&mut Surface::new(buf, stride), 0, 0);
+    }
+
+    fn draw_surface(&self, surface: &mut Surface, origin_x: i32, origin_y: i32) {
+        let offset_x = origin_x + self.transform.x;
+        let offset_y = origin_y + self.transform.y;
+        let clip_depth = surface.clip_depth();
+
+        if let Some(widget) = &self.widget {
+            let (width, height) = (self.transform.width, self.transform.height);
+            let (bx0, by0, bx1, by1) = surface.bounds();
+            let x1 = (offset_x + width as i32).min(bx1);
+            let y1 = (offset_y + height as i32).min(by1);
+            widget.set_component(Layout {
+                x: offset_x,
+                y: offset_y,
+                width,
+                height,
+                visible: (offset_x.max(bx0), offset_y.max(by0), x1, y1),
+            });
+        }
+
+        for m in &self.render_stack {
+            match m {
+                /* ───────────── Rectangle ───────────── */
+                RenderMethod::Rectangle(px, py, width, height, color) => {
+                    let xs = offset_x + *px;
+                    let ys = offset_y + *py;
+                    let shader =
+                        Shader::new(color, xs as f32, ys as f32, *width as f32, *height as f32);
+
+                    for y in ys..ys + *height as i32 {
+                        surface.shade_span(y, xs, xs + *width as i32, &shader);
+                    }
+                }
+
+                /* ─────────── Rounded Rectangle ─────────── */
+                RenderMethod::RoundedRectangle(px, py, width, height, radius, color) => {
+                    let (ww, hh) = (*width as i32, *height as i32);
+                    let sx = offset_x + *px;
+                    let sy = offset_y + *py;
+                    let (bx0, by0, bx1, by1) = surface.bounds();
+                    let shader = Shader::new(color, sx as f32, sy as f32, ww as f32, hh as f32);
+                    // Rows clear of every corner are filled as plain spans.
+                    let top = radius.top_left.max(radius.top_right) as i32;
+                    let bottom = hh - radius.bottom_left.max(radius.bottom_right) as i32;
+
+                    for y in (by0 - sy).max(0)..hh.min(by1 - sy) {
+                        if y >= top && y < bottom {
+                            surface.shade_span(sy + y, sx, sx + ww, &shader);
+                            continue;
+                        }
+                        for x in (bx0 - sx).max(0)..ww.min(bx1 - sx) {
+                            let coverage = corner_coverage(x, y, *width, *height, *radius);
+                            if coverage > 0.0 {
+                                surface.blend_shader(sx + x, sy + y, &shader, coverage);
+                            }
+                        }
+                    }
+                }
+
+                /* ───────────────── Border ──────────────── */
+                RenderMethod::Border(px, py, width, height, radius, border) => {
+                    border::draw(
+                        surface,
+                        offset_x + *px,
+                        offset_y + *py,
+                        *width,
+                        *height,
+                        *radius,
+                        border,
+                    );
+                }
+
+                /* ──────────────── Effects ──────────────── */
+                RenderMethod::BoxShadow(px, py, width, height, radius, shadow) => {
+                    effects::draw_shadow(
+                        surface,
+                        offset_x + *px,
+                        offset_y + *py,
+                        *width,
+                        *height,
+                        *radius,
+                        shadow,
+                    );
+                }
+
+                RenderMethod::BackdropBlur(px, py, width, height, radius, blur) => {
+                    effects::backdrop_blur(
+                        surface,
+                        offset_x + *px,
+                        offset_y + *py,
+                        *width,
+                        *height,
+                        *radius,
+                        *blur,
+                    );
+                }
+
+                /* ───────────────── Image ───────────────── */
+                RenderMethod::Image(px, py, width, height, data, filter) => {
+                    image::draw(
+                        surface,
+                        offset_x + *px,
+                        offset_y + *py,
+                        *width,
+                        *height,
+                        data,
+                        *filter,
+                    );
+                }
+
+                /* ───────────────── Paths ───────────────── */
+                RenderMethod::FillPath(p, rule, color) => {
+                    path::fill(surface, p, offset_x, offset_y, *rule, color);
+                }
+
+                RenderMethod::StrokePath(p, width, color) => {
+                    path::stroke(surface, p, offset_x, offset_y, *width, color);
+                }
+
+                /* ────────────────── Text ───────────────── */
+                RenderMethod::Text(text, px, py, style) => {
+                    text::draw(surface, text, offset_x + *px, offset_y + *py, style);
+                }
+
+                /* ────────────────── Clip ───────────────── */
+                RenderMethod::PushClip(px, py, width, height, radius) => {
+                    surface.push_clip(Clip {
+                        x: offset_x + *px,
+                        y: offset_y + *py,
+                        width: *width,
+                        height: *height,
+                        radius: *radius,
+                    });
+                }
+
+                RenderMethod::PopClip => {
+                    if surface.clip_depth() > clip_depth {
+                        surface.pop_clip();
+                    }
+                }
+
+                /* ───────────────── Merge ───────────────── */
+                RenderMethod::Merge(scope) => {
+                    scope.draw_surface(surface, offset_x, offset_y);
+                }
+            }
+        }
+
+        surface.restore_clip(clip_depth);
+    }
+}
+
+// Update or variable functions
+
+impl RenderScope {
+    pub fn resize_if_needed(&mut self, w: usize, h: usize) {
+        if self.parent_width != w || self.parent_height != h {
+            self.parent_width = w;
+            self.parent_height = h;
+            self.buffer.resize(w * h, 0);
+        }
+    }
+
+    pub fn set_transform(&mut self, transform: &Transform) {
+        transform.use_dimensions(self.parent_width, self.parent_height, &mut self.transform);
+        transform.use_position(self.parent_width, self.parent_height, &mut self.transform);
+    }
+
+    /// Moves the scope by `(x, y)` within its parent, after layout.
+    pub fn translate(&mut self, x: i32, y: i32) {
+        self.transform.x += x;
+        self.transform.y += y;
+    }
+
+    pub fn get_transform(&self) -> RawTransform {
+        self.transform.clone()
+    }
+
+    pub fn update_size(&mut self) {
+        self.max_size.0 = self.max_size.0.max(self.transform.width);
+        self.max_size.1 = self.max_size.1.max(self.transform.height);
+    }
+
+    pub fn clear(&mut self) {
+        self.render_stack.clear();
+        self.widget = None;
+        self.transform.width = 0;
+        self.transform.height = 0;
+    }
+
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.transform.width, self.transform.height)
+    }
+
+    pub fn get_max_size(&self) -> (usize, usize) {
+        self.max_size
+    }
+
+    pub fn get_size_or_parent(&self) -> (usize, usize) {
+        (
+            if self.transform.width > 0 {
+                self.transform.width
+            } else {
+                self.parent_width
+            },
+            if self.transform.height > 0 {
+                self.transform.height
+            } else {
+                self.parent_height
+            },
+        )
+    }
+
+    pub fn get_parent_size(&self) -> (usize, usize) {
+        (self.parent_width, self.parent_height)
+    }
+
+    pub fn get_buffer1d(&self) -> &Vec<u32> {
+        &self.buffer
+    }
+
+    pub fn get_buffer(&self) -> Vec<&[u32]> {
+        self.buffer.chunks(self.parent_width).collect()
+    }
+
+    pub fn get_buffer_mut(&mut self) -> Vec<&mut [u32]> {
+        self.buffer.chunks_mut(self.parent_width).collect()
+    }
+
+    pub fn clear_buffer(&mut self) {
+        self.buffer.fill(0);
+    }
+}
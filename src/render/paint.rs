@@ -0,0 +1,141 @@
+use crate::{color::Color, paint::Paint};
+
+/// 4x4 ordered dither thresholds `0..16`; `color_at` centers them on zero.
+const BAYER: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+enum Kind {
+    Solid(Color),
+    /// Start point and step per pixel along the gradient line.
+    Linear {
+        x: f32,
+        y: f32,
+        dx: f32,
+        dy: f32,
+    },
+    Radial {
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+}
+
+/// A [`Paint`] placed over a box in buffer space, ready to be sampled per pixel.
+pub(crate) struct Shader<'a> {
+    kind: Kind,
+    stops: &'a [(f32, Color)],
+}
+
+impl<'a> Shader<'a> {
+    /// Spreads `paint` over the `width` x `height` box at `(x, y)`.
+    pub fn new(paint: &'a Paint, x: f32, y: f32, width: f32, height: f32) -> Self {
+        let (w, h) = (width.max(1.0), height.max(1.0));
+        match paint {
+            Paint::Solid(color) => Self {
+                kind: Kind::Solid(*color),
+                stops: &[],
+            },
+            Paint::Linear { angle, stops } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                // Long enough that the box corners land on the first and last stop.
+                let length = (w * sin).abs() + (h * cos).abs();
+                let (dx, dy) = (sin / length, -cos / length);
+                let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+                Self {
+                    kind: Kind::Linear {
+                        x: cx - dx * length * length / 2.0,
+                        y: cy - dy * length * length / 2.0,
+                        dx,
+                        dy,
+                    },
+                    stops,
+                }
+            }
+            Paint::Radial { center, stops } => {
+                let (cx, cy) = (x + center.0 * w, y + center.1 * h);
+                let far_x = (cx - x).max(x + w - cx);
+                let far_y = (cy - y).max(y + h - cy);
+                Self {
+                    kind: Kind::Radial {
+                        x: cx,
+                        y: cy,
+                        radius: (far_x * far_x + far_y * far_y).sqrt().max(1.0),
+                    },
+                    stops,
+                }
+            }
+        }
+    }
+
+    /// The color of every pixel, if it doesn't vary.
+    pub fn solid(&self) -> Option<Color> {
+        match self.kind {
+            Kind::Solid(color) => Some(color),
+            _ if self.stops.len() == 1 => Some(self.stops[0].1),
+            _ if self.stops.is_empty() => Some(Color::TRANSPARENT),
+            _ => None,
+        }
+    }
+
+    /// The dithered color of the pixel at `(x, y)`.
+    pub fn color_at(&self, x: i32, y: i32) -> Color {
+        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+        let t = match self.kind {
+            Kind::Solid(color) => return color,
+            Kind::Linear { x, y, dx, dy } => (px - x) * dx + (py - y) * dy,
+            Kind::Radial { x, y, radius } => ((px - x).powi(2) + (py - y).powi(2)).sqrt() / radius,
+        };
+        let dither = (BAYER[y as usize & 3][x as usize & 3] + 0.5) / 16.0 - 0.5;
+        self.sample(t.clamp(0.0, 1.0), dither)
+    }
+
+    /// Interpolates the stops at `t`, see [`mix`].
+    fn sample(&self, t: f32, dither: f32) -> Color {
+        let stops = self.stops;
+        let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+            return Color::TRANSPARENT;
+        };
+        let (a, b, f) = if t <= first.0 {
+            (first.1, first.1, 0.0)
+        } else if t >= last.0 {
+            (last.1, last.1, 0.0)
+        } else {
+            let i = stops
+                .iter()
+                .position(|s| s.0 > t)
+                .unwrap_or(stops.len() - 1);
+            let (lo, hi) = (stops[i - 1], stops[i]);
+            let span = hi.0 - lo.0;
+            let f = if span > 0.0 { (t - lo.0) / span } else { 1.0 };
+            (lo.1, hi.1, f)
+        };
+
+        mix(a, b, f, dither)
+    }
+}
+
+/// `a` moved towards `b` by `f`, interpolated with premultiplied alpha so
+/// fading to transparent doesn't darken the colors in between. `dither` is
+/// added to every channel before rounding.
+pub(crate) fn mix(a: Color, b: Color, f: f32, dither: f32) -> Color {
+    let alpha_a = a.alpha() as f32 / 255.0;
+    let alpha_b = b.alpha() as f32 / 255.0;
+    let alpha = alpha_a + (alpha_b - alpha_a) * f;
+    if alpha <= 0.0 {
+        return Color::TRANSPARENT;
+    }
+    let channel = |ca: u8, cb: u8| {
+        let v = (ca as f32 * alpha_a + (cb as f32 * alpha_b - ca as f32 * alpha_a) * f) / alpha;
+        (v + dither).round().clamp(0.0, 255.0) as u8
+    };
+    Color::rgba(
+        channel(a.red(), b.red()),
+        channel(a.green(), b.green()),
+        channel(a.blue(), b.blue()),
+        (alpha * 255.0 + dither).round().clamp(0.0, 255.0) as u8,
+    )
+}
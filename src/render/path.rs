@@ -0,0 +1,513 @@
+use std::f32::consts::PI;
+
+use crate::{
+    paint::Paint,
+    render::{paint::Shader, surface::Surface},
+};
+
+/// Curves are split into lines no further than this from the real curve, in pixels.
+const TOLERANCE: f32 = 0.2;
+/// Sample rows per pixel when filling; columns get exact coverage.
+const SUBSAMPLES: usize = 5;
+
+/// Decides which areas of a self-intersecting or nested path are inside.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside where the outline winds around a point any number of times.
+    #[default]
+    NonZero,
+    /// Inside where a ray from a point crosses the outline an odd number of times.
+    EvenOdd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    MoveTo(f32, f32),
+    LineTo(f32, f32),
+    QuadTo(f32, f32, f32, f32),
+    CubicTo(f32, f32, f32, f32, f32, f32),
+    /// Center, radii, start angle and sweep, in radians.
+    Arc(f32, f32, f32, f32, f32, f32),
+    Close,
+}
+
+/// An outline made of lines, Bézier curves and elliptical arcs, for
+/// [`RenderScope::fill_path`](super::RenderScope::fill_path) and
+/// [`RenderScope::stroke_path`](super::RenderScope::stroke_path).
+///
+/// Coordinates are relative to the scope it is drawn in. Angles are in
+/// radians, starting at the positive x axis and turning clockwise on screen.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+/// A flattened subpath.
+struct Polyline {
+    points: Vec<(f32, f32)>,
+    closed: bool,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new subpath at `(x, y)`.
+    pub fn move_to(mut self, x: f32, y: f32) -> Self {
+        self.segments.push(Segment::MoveTo(x, y));
+        self
+    }
+
+    pub fn line_to(mut self, x: f32, y: f32) -> Self {
+        self.segments.push(Segment::LineTo(x, y));
+        self
+    }
+
+    /// A quadratic Bézier curve through control point `(cx, cy)` to `(x, y)`.
+    pub fn quad_to(mut self, cx: f32, cy: f32, x: f32, y: f32) -> Self {
+        self.segments.push(Segment::QuadTo(cx, cy, x, y));
+        self
+    }
+
+    /// A cubic Bézier curve through control points `(c1x, c1y)` and `(c2x, c2y)` to `(x, y)`.
+    pub fn cubic_to(mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> Self {
+        self.segments
+            .push(Segment::CubicTo(c1x, c1y, c2x, c2y, x, y));
+        self
+    }
+
+    /// An elliptical arc around `(cx, cy)`, joined to the current point by a line.
+    pub fn arc(mut self, cx: f32, cy: f32, rx: f32, ry: f32, start: f32, sweep: f32) -> Self {
+        self.segments
+            .push(Segment::Arc(cx, cy, rx, ry, start, sweep));
+        self
+    }
+
+    /// Joins the current subpath back to its start.
+    pub fn close(mut self) -> Self {
+        self.segments.push(Segment::Close);
+        self
+    }
+
+    /// Straight lines through `points`, closed if `closed` is set.
+    pub fn polyline(points: &[(f32, f32)], closed: bool) -> Self {
+        let mut path = Self::new();
+        for (i, &(x, y)) in points.iter().enumerate() {
+            path = if i == 0 {
+                path.move_to(x, y)
+            } else {
+                path.line_to(x, y)
+            };
+        }
+        if closed {
+            path = path.close();
+        }
+        path
+    }
+
+    pub fn circle(cx: f32, cy: f32, r: f32) -> Self {
+        Self::ellipse(cx, cy, r, r)
+    }
+
+    pub fn ellipse(cx: f32, cy: f32, rx: f32, ry: f32) -> Self {
+        Self::new().arc(cx, cy, rx, ry, 0.0, 2.0 * PI).close()
+    }
+
+    /// The smallest box `(x0, y0, x1, y1)` around every point and control point.
+    pub fn bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        let mut points = Vec::new();
+        for segment in &self.segments {
+            match *segment {
+                Segment::MoveTo(x, y) | Segment::LineTo(x, y) => points.push((x, y)),
+                Segment::QuadTo(cx, cy, x, y) => points.extend([(cx, cy), (x, y)]),
+                Segment::CubicTo(c1x, c1y, c2x, c2y, x, y) => {
+                    points.extend([(c1x, c1y), (c2x, c2y), (x, y)])
+                }
+                Segment::Arc(cx, cy, rx, ry, ..) => {
+                    points.extend([(cx - rx, cy - ry), (cx + rx, cy + ry)])
+                }
+                Segment::Close => {}
+            }
+        }
+        points.iter().fold(None, |bounds, &(x, y)| {
+            let (x0, y0, x1, y1) = bounds.unwrap_or((x, y, x, y));
+            Some((x0.min(x), y0.min(y), x1.max(x), y1.max(y)))
+        })
+    }
+
+    /// Splits curves into lines, moving every point by `(dx, dy)`.
+    fn flatten(&self, dx: f32, dy: f32) -> Vec<Polyline> {
+        let mut lines: Vec<Polyline> = Vec::new();
+        let mut current = Polyline {
+            points: Vec::new(),
+            closed: false,
+        };
+        let mut last = (0.0, 0.0);
+
+        for segment in &self.segments {
+            match *segment {
+                Segment::MoveTo(x, y) => {
+                    let done = std::mem::replace(
+                        &mut current,
+                        Polyline {
+                            points: vec![(x + dx, y + dy)],
+                            closed: false,
+                        },
+                    );
+                    lines.push(done);
+                    last = (x + dx, y + dy);
+                }
+                Segment::LineTo(x, y) => {
+                    last = (x + dx, y + dy);
+                    current.points.push(last);
+                }
+                Segment::QuadTo(cx, cy, x, y) => {
+                    let (p0, p1, p2) = (last, (cx + dx, cy + dy), (x + dx, y + dy));
+                    let dd = length(p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1);
+                    let n = steps(dd / 8.0);
+                    if current.points.is_empty() {
+                        current.points.push(p0);
+                    }
+                    for i in 1..=n {
+                        let t = i as f32 / n as f32;
+                        let u = 1.0 - t;
+                        current.points.push((
+                            u * u * p0.0 + 2.0 * u * t * p1.0 + t * t * p2.0,
+                            u * u * p0.1 + 2.0 * u * t * p1.1 + t * t * p2.1,
+                        ));
+                    }
+                    last = p2;
+                }
+                Segment::CubicTo(c1x, c1y, c2x, c2y, x, y) => {
+                    let (p0, p1, p2, p3) = (
+                        last,
+                        (c1x + dx, c1y + dy),
+                        (c2x + dx, c2y + dy),
+                        (x + dx, y + dy),
+                    );
+                    let dd = length(p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1)
+                        .max(length(p1.0 - 2.0 * p2.0 + p3.0, p1.1 - 2.0 * p2.1 + p3.1));
+                    let n = steps(dd * 0.75);
+                    if current.points.is_empty() {
+                        current.points.push(p0);
+                    }
+                    for i in 1..=n {
+                        let t = i as f32 / n as f32;
+                        let u = 1.0 - t;
+                        let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+                        current.points.push((
+                            a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+                            a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+                        ));
+                    }
+                    last = p3;
+                }
+                Segment::Arc(cx, cy, rx, ry, start, sweep) => {
+                    let r = rx.abs().max(ry.abs());
+                    let step = if r > TOLERANCE {
+                        2.0 * (1.0 - TOLERANCE / r).acos()
+                    } else {
+                        PI / 2.0
+                    };
+                    let n = ((sweep.abs() / step).ceil() as usize).clamp(1, 1024);
+                    for i in 0..=n {
+                        let a = start + sweep * i as f32 / n as f32;
+                        last = (cx + dx + rx * a.cos(), cy + dy + ry * a.sin());
+                        current.points.push(last);
+                    }
+                }
+                Segment::Close => {
+                    current.closed = true;
+                    let start = current.points.first().copied();
+                    let done = std::mem::replace(
+                        &mut current,
+                        Polyline {
+                            points: start.into_iter().collect(),
+                            closed: false,
+                        },
+                    );
+                    lines.push(done);
+                    if let Some(start) = start {
+                        last = start;
+                    }
+                }
+            }
+        }
+        lines.push(current);
+
+        for line in &mut lines {
+            line.points.dedup();
+            if line.closed && line.points.len() > 1 && line.points.first() == line.points.last() {
+                line.points.pop();
+            }
+        }
+        lines.retain(|l| l.points.len() > 1);
+        lines
+    }
+}
+
+fn length(x: f32, y: f32) -> f32 {
+    (x * x + y * y).sqrt()
+}
+
+/// Line count keeping a curve with second-difference error `error` within tolerance.
+fn steps(error: f32) -> usize {
+    ((error / TOLERANCE).sqrt().ceil() as usize).clamp(1, 1024)
+}
+
+/// Fills `path`, moved by `(dx, dy)` into buffer space.
+pub(crate) fn fill(
+    surface: &mut Surface,
+    path: &Path,
+    dx: i32,
+    dy: i32,
+    rule: FillRule,
+    paint: &Paint,
+) {
+    let polygons: Vec<Vec<(f32, f32)>> = path
+        .flatten(dx as f32, dy as f32)
+        .into_iter()
+        .map(|l| l.points)
+        .collect();
+    fill_polygons(surface, &polygons, rule, paint);
+}
+
+/// Strokes `path`, moved by `(dx, dy)` into buffer space, with round joins and flat ends.
+pub(crate) fn stroke(
+    surface: &mut Surface,
+    path: &Path,
+    dx: i32,
+    dy: i32,
+    width: f32,
+    paint: &Paint,
+) {
+    let hw = width / 2.0;
+    if hw <= 0.0 {
+        return;
+    }
+
+    // Every piece is wound the same way, so the non-zero rule unions them.
+    let mut polygons = Vec::new();
+    for line in path.flatten(dx as f32, dy as f32) {
+        let points = &line.points;
+        let count = points.len();
+        let segments = if line.closed { count } else { count - 1 };
+
+        for i in 0..segments {
+            let (p, q) = (points[i], points[(i + 1) % count]);
+            let (nx, ny) = normal(p, q, hw);
+            polygons.push(vec![
+                (p.0 + nx, p.1 + ny),
+                (q.0 + nx, q.1 + ny),
+                (q.0 - nx, q.1 - ny),
+                (p.0 - nx, p.1 - ny),
+            ]);
+        }
+
+        let joins = if line.closed { 0..count } else { 1..count - 1 };
+        for i in joins {
+            let prev = points[(i + count - 1) % count];
+            let (p, next) = (points[i], points[(i + 1) % count]);
+            polygons.push(round_join(prev, p, next, hw));
+        }
+    }
+
+    for polygon in &mut polygons {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    fill_polygons(surface, &polygons, FillRule::NonZero, paint);
+}
+
+/// The normal of `p -> q`, `hw` long.
+fn normal(p: (f32, f32), q: (f32, f32), hw: f32) -> (f32, f32) {
+    let len = length(q.0 - p.0, q.1 - p.1);
+    (-(q.1 - p.1) / len * hw, (q.0 - p.0) / len * hw)
+}
+
+/// The wedge of a circle around `p` filling the gap on the outside of the turn.
+fn round_join(prev: (f32, f32), p: (f32, f32), next: (f32, f32), hw: f32) -> Vec<(f32, f32)> {
+    let (ax, ay) = normal(prev, p, hw);
+    let (bx, by) = normal(p, next, hw);
+    // Turning clockwise leaves the gap on the other side.
+    let (ax, ay, bx, by) = if ax * by - ay * bx > 0.0 {
+        (-ax, -ay, -bx, -by)
+    } else {
+        (ax, ay, bx, by)
+    };
+
+    let (start, end) = (ay.atan2(ax), by.atan2(bx));
+    let mut sweep = end - start;
+    if sweep > PI {
+        sweep -= 2.0 * PI;
+    } else if sweep < -PI {
+        sweep += 2.0 * PI;
+    }
+    let step = if hw > TOLERANCE {
+        2.0 * (1.0 - TOLERANCE / hw).acos()
+    } else {
+        PI / 2.0
+    };
+    let n = (sweep.abs() / step).ceil() as usize;
+
+    let mut wedge = vec![p];
+    for i in 0..=n.max(1) {
+        let a = start + sweep * i as f32 / n.max(1) as f32;
+        wedge.push((p.0 + hw * a.cos(), p.1 + hw * a.sin()));
+    }
+    wedge
+}
+
+fn signed_area(points: &[(f32, f32)]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    winding: i32,
+}
+
+/// Scanline fill of closed `polygons`, sampling a few rows per pixel and
+/// giving partly covered columns their exact share.
+fn fill_polygons(
+    surface: &mut Surface,
+    polygons: &[Vec<(f32, f32)>],
+    rule: FillRule,
+    paint: &Paint,
+) {
+    if paint.is_transparent() {
+        return;
+    }
+
+    let mut edges = Vec::new();
+    for polygon in polygons {
+        for i in 0..polygon.len() {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            if a.1 == b.1 {
+                continue;
+            }
+            let (top, bottom, winding) = if a.1 < b.1 { (a, b, 1) } else { (b, a, -1) };
+            edges.push(Edge {
+                x0: top.0,
+                y0: top.1,
+                x1: bottom.0,
+                y1: bottom.1,
+                winding,
+            });
+        }
+    }
+    if edges.is_empty() {
+        return;
+    }
+    edges.sort_by(|a, b| a.y0.total_cmp(&b.y0));
+
+    let (bx0, by0, bx1, by1) = surface.bounds();
+    let min_x = edges
+        .iter()
+        .map(|e| e.x0.min(e.x1))
+        .fold(f32::MAX, f32::min);
+    let max_x = edges
+        .iter()
+        .map(|e| e.x0.max(e.x1))
+        .fold(f32::MIN, f32::max);
+    let min_y = edges[0].y0;
+    let max_y = edges.iter().map(|e| e.y1).fold(f32::MIN, f32::max);
+
+    let x_start = (min_x.floor() as i32).max(bx0);
+    let x_end = (max_x.ceil() as i32).min(bx1);
+    let y_start = (min_y.floor() as i32).max(by0);
+    let y_end = (max_y.ceil() as i32).min(by1);
+    if x_start >= x_end || y_start >= y_end {
+        return;
+    }
+
+    let shader = Shader::new(paint, min_x, min_y, max_x - min_x, max_y - min_y);
+    let mut coverage = vec![0.0f32; (x_end - x_start) as usize];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    let mut next_edge = 0;
+    let weight = 1.0 / SUBSAMPLES as f32;
+
+    for y in y_start..y_end {
+        let (row_top, row_bottom) = (y as f32, y as f32 + 1.0);
+        while next_edge < edges.len() && edges[next_edge].y0 < row_bottom {
+            active.push(next_edge);
+            next_edge += 1;
+        }
+        active.retain(|&i| edges[i].y1 > row_top);
+        if active.is_empty() {
+            continue;
+        }
+
+        for s in 0..SUBSAMPLES {
+            let sy = row_top + (s as f32 + 0.5) * weight;
+            crossings.clear();
+            for &i in &active {
+                let e = &edges[i];
+                if sy >= e.y0 && sy < e.y1 {
+                    let x = e.x0 + (sy - e.y0) * (e.x1 - e.x0) / (e.y1 - e.y0);
+                    crossings.push((x, e.winding));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                let inside = match rule {
+                    FillRule::NonZero => winding != 0,
+                    FillRule::EvenOdd => winding % 2 != 0,
+                };
+                if inside {
+                    add_span(
+                        &mut coverage,
+                        pair[0].0 - x_start as f32,
+                        pair[1].0 - x_start as f32,
+                        weight,
+                    );
+                }
+            }
+        }
+
+        for (i, c) in coverage.iter_mut().enumerate() {
+            if *c > 0.0 {
+                surface.blend_shader(x_start + i as i32, y, &shader, c.min(1.0));
+                *c = 0.0;
+            }
+        }
+    }
+}
+
+/// Adds `weight` times the covered part of every column between `x0` and `x1`.
+fn add_span(coverage: &mut [f32], x0: f32, x1: f32, weight: f32) {
+    let x0 = x0.max(0.0);
+    let x1 = x1.min(coverage.len() as f32);
+    if x0 >= x1 {
+        return;
+    }
+
+    let (i0, i1) = (x0 as usize, x1 as usize);
+    if i0 == i1 {
+        coverage[i0] += (x1 - x0) * weight;
+        return;
+    }
+    coverage[i0] += (i0 as f32 + 1.0 - x0) * weight;
+    for c in &mut coverage[i0 + 1..i1] {
+        *c += weight;
+    }
+    if i1 < coverage.len() {
+        coverage[i1] += (x1 - i1 as f32) * weight;
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{color::Color, render::paint::Shader, style::Corners};
+
+/// A clip region in buffer space, optionally with rounded corners.
+#[derive(Debug, Clone, Copy)]
+pub struct Clip {
+    pub x: i32,
+    pub y: i32,
+    pub width: usize,
+    pub height: usize,
+    pub radius: Corners,
+}
+
+/// The pixel buffer a [`RenderScope`](super::RenderScope) draws into, with its clip stack.
+pub(crate) struct Surface<'a> {
+    buf: &'a mut [u32],
+    stride: usize,
+    height: usize,
+    clips: Vec<Clip>,
+    bounds: (i32, i32, i32, i32),
+}
+
+impl<'a> Surface<'a> {
+    pub fn new(buf: &'a mut [u32], stride: usize) -> Self {
+        let height = buf.len() / stride.max(1);
+        Self {
+            buf,
+            stride,
+            height,
+            clips: Vec::new(),
+            bounds: (0, 0, stride as i32, height as i32),
+        }
+    }
+
+    /// The visible area as `(x0, y0, x1, y1)`, exclusive at the end.
+    pub fn bounds(&self) -> (i32, i32, i32, i32) {
+        self.bounds
+    }
+
+    /// The whole buffer's size, ignoring clips.
+    pub fn size(&self) -> (usize, usize) {
+        (self.stride, self.height)
+    }
+
+    /// The pixel at `(x, y)`, which must be inside the buffer.
+    pub fn pixel(&self, x: usize, y: usize) -> u32 {
+        self.buf[y * self.stride + x]
+    }
+
+    pub fn clip_depth(&self) -> usize {
+        self.clips.len()
+    }
+
+    pub fn push_clip(&mut self, clip: Clip) {
+        self.clips.push(clip);
+        self.update_bounds();
+    }
+
+    pub fn pop_clip(&mut self) {
+        self.clips.pop();
+        self.update_bounds();
+    }
+
+    /// Drops clips pushed after `depth`, so unbalanced pushes don't leak out of a scope.
+    pub fn restore_clip(&mut self, depth: usize) {
+        if self.clips.len() > depth {
+            self.clips.truncate(depth);
+            self.update_bounds();
+        }
+    }
+
+    fn update_bounds(&mut self) {
+        self.bounds = self.clips.iter().fold(
+            (0, 0, self.stride as i32, self.height as i32),
+            |(x0, y0, x1, y1), c| {
+                (
+                    x0.max(c.x),
+                    y0.max(c.y),
+                    x1.min(c.x + c.width as i32),
+                    y1.min(c.y + c.height as i32),
+                )
+            },
+        );
+    }
+
+    /// Whether any clip has rounded corners, so pixels need a coverage test.
+    pub fn is_rounded(&self) -> bool {
+        self.clips.iter().any(|c| !c.radius.is_zero())
+    }
+
+    /// Coverage of the pixel at `(x, y)` by the rounded corners of every clip.
+    pub fn clip_coverage(&self, x: i32, y: i32) -> f32 {
+        self.clips
+            .iter()
+            .filter(|c| !c.radius.is_zero())
+            .map(|c| corner_coverage(x - c.x, y - c.y, c.width, c.height, c.radius))
+            .product()
+    }
+
+    /// Blends `color` into the pixel at `(x, y)` if it is inside the clip.
+    pub fn blend(&mut self, x: i32, y: i32, color: Color, coverage: f32) {
+        let (x0, y0, x1, y1) = self.bounds;
+        if x < x0 || y < y0 || x >= x1 || y >= y1 {
+            return;
+        }
+
+        let coverage = if self.is_rounded() {
+            coverage * self.clip_coverage(x, y)
+        } else {
+            coverage
+        };
+        let p = &mut self.buf[y as usize * self.stride + x as usize];
+        *p = color.blend(*p, coverage);
+    }
+
+    /// Fills `x0..x1` on row `y`, clipped.
+    pub fn fill_span(&mut self, y: i32, x0: i32, x1: i32, color: Color) {
+        let (bx0, by0, bx1, by1) = self.bounds;
+        if y < by0 || y >= by1 {
+            return;
+        }
+        let (xs, xe) = (x0.max(bx0), x1.min(bx1));
+        if xs >= xe {
+            return;
+        }
+
+        if self.is_rounded() {
+            for x in xs..xe {
+                self.blend(x, y, color, 1.0);
+            }
+            return;
+        }
+
+        let start = y as usize * self.stride + xs as usize;
+        let row = &mut self.buf[start..start + (xe - xs) as usize];
+        if color.is_opaque() {
+            row.fill(color.0 & 0xFFFFFF);
+        } else {
+            for p in row {
+                *p = color.blend(*p, 1.0);
+            }
+        }
+    }
+
+    /// Blends `shader`'s color at `(x, y)` into the pixel if it is inside the clip.
+    pub fn blend_shader(&mut self, x: i32, y: i32, shader: &Shader, coverage: f32) {
+        self.blend(x, y, shader.color_at(x, y), coverage);
+    }
+
+    /// Fills `x0..x1` on row `y` with `shader`, clipped.
+    pub fn shade_span(&mut self, y: i32, x0: i32, x1: i32, shader: &Shader) {
+        if let Some(color) = shader.solid() {
+            self.fill_span(y, x0, x1, color);
+            return;
+        }
+        let (bx0, by0, bx1, by1) = self.bounds;
+        if y < by0 || y >= by1 {
+            return;
+        }
+        for x in x0.max(bx0)..x1.min(bx1) {
+            self.blend(x, y, shader.color_at(x, y), 1.0);
+        }
+    }
+}
+
+/// Anti-aliased coverage of local pixel `(x, y)` by a `width`×`height` box with
+/// rounded `corners`. Pixels outside the box have no coverage.
+pub(crate) fn corner_coverage(
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+    corners: Corners,
+) -> f32 {
+    let radii = corners.to_array().map(|r| (r as f32, r as f32));
+    let d = box_distance(
+        x as f32 + 0.5,
+        y as f32 + 0.5,
+        width as f32,
+        height as f32,
+        radii,
+    );
+    (0.5 - d).clamp(0.0, 1.0)
+}
+
+/// Approximate signed distance from `(px, py)` to the edge of a `w`×`h` box
+/// whose corners are quarter ellipses with `(rx, ry)` radii, clockwise from the
+/// top left. Negative inside; exact enough within a pixel of the edge.
+pub(crate) fn box_distance(px: f32, py: f32, w: f32, h: f32, radii: [(f32, f32); 4]) -> f32 {
+    let (right, bottom) = (px > w / 2.0, py > h / 2.0);
+    let corner = match (right, bottom) {
+        (false, false) => 0,
+        (true, false) => 1,
+        (true, true) => 2,
+        (false, true) => 3,
+    };
+    let (rx, ry) = radii[corner];
+    let (rx, ry) = (rx.clamp(0.0, w / 2.0), ry.clamp(0.0, h / 2.0));
+
+    // How far past the center of the corner's ellipse, towards the corner.
+    let dx = if right { px - (w - rx) } else { rx - px };
+    let dy = if bottom { py - (h - ry) } else { ry - py };
+    if dx > 0.0 && dy > 0.0 && rx > 0.0 && ry > 0.0 {
+        let (nx, ny) = (dx / rx, dy / ry);
+        let gradient = 2.0 * ((nx / rx).powi(2) + (ny / ry).powi(2)).sqrt();
+        if gradient <= 0.0 {
+            return -rx.min(ry);
+        }
+        return (nx * nx + ny * ny - 1.0) / gradient;
+    }
+
+    let out_x = (-px).max(px - w);
+    let out_y = (-py).max(py - h);
+    if out_x > 0.0 && out_y > 0.0 {
+        (out_x * out_x + out_y * out_y).sqrt()
+    } else {
+        out_x.max(out_y)
+    }
+}
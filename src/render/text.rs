@@ -0,0 +1,191 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use once_cell::sync::Lazy;
+use rusttype::{point, GlyphId, PositionedGlyph};
+
+use crate::{
+    render::{font, paint::Shader, surface::Surface},
+    style::TextStyle,
+};
+
+/// Horizontal shift of synthesized italics per pixel above the baseline.
+const ITALIC_SLANT: f32 = 0.2;
+/// Glyphs are rasterized at this many horizontal and vertical subpixel offsets.
+const SUBPIXEL_STEPS: f32 = 4.0;
+/// Past this many cached bitmaps, the least recently used quarter is dropped.
+const GLYPH_CACHE_LIMIT: usize = 8192;
+
+#[derive(PartialEq, Eq, Hash)]
+struct GlyphKey {
+    font: usize,
+    size: u32,
+    glyph: GlyphId,
+    subpixel: (u8, u8),
+    smear: usize,
+}
+
+/// A rasterized glyph, placed relative to the whole pixel its origin falls in.
+struct GlyphBitmap {
+    left: i32,
+    top: i32,
+    width: usize,
+    height: usize,
+    coverage: Vec<u8>,
+}
+
+/// Bitmaps with the tick they were last used at, evicted like the layout cache
+/// so glyphs drawn every frame survive a burst of new ones.
+#[derive(Default)]
+struct GlyphCache {
+    bitmaps: HashMap<GlyphKey, (Arc<GlyphBitmap>, u64)>,
+    tick: u64,
+    /// Bitmaps rendered so far, for telling hits from misses in tests.
+    rasterized: usize,
+}
+
+impl GlyphCache {
+    fn get(&mut self, key: &GlyphKey) -> Option<Arc<GlyphBitmap>> {
+        self.tick += 1;
+        let (bitmap, used) = self.bitmaps.get_mut(key)?;
+        *used = self.tick;
+        Some(bitmap.clone())
+    }
+
+    fn insert(&mut self, key: GlyphKey, bitmap: Arc<GlyphBitmap>) {
+        if self.bitmaps.len() >= GLYPH_CACHE_LIMIT {
+            self.evict(GLYPH_CACHE_LIMIT / 4);
+        }
+        self.bitmaps.insert(key, (bitmap, self.tick));
+        self.rasterized += 1;
+    }
+
+    /// Drops the `count` least recently used bitmaps.
+    fn evict(&mut self, count: usize) {
+        let mut ticks: Vec<u64> = self.bitmaps.values().map(|(_, used)| *used).collect();
+        if count == 0 || ticks.is_empty() {
+            return;
+        }
+        let nth = count.min(ticks.len()) - 1;
+        let cutoff = *ticks.select_nth_unstable(nth).1;
+        self.bitmaps.retain(|_, (_, used)| *used > cutoff);
+    }
+}
+
+static GLYPHS: Lazy<Mutex<GlyphCache>> = Lazy::new(Default::default);
+
+/// How many glyph bitmaps were rendered rather than taken from the cache.
+#[cfg(any(test, feature = "testing"))]
+pub(crate) fn glyphs_rasterized() -> usize {
+    GLYPHS.lock().unwrap().rasterized
+}
+
+/// Draws one line of `text` with its top-left corner at `(x, y)` in buffer space.
+pub(crate) fn draw(surface: &mut Surface, text: &str, x: i32, y: i32, style: &TextStyle) {
+    let run = font::layout(text, style);
+    let baseline = y as f32 + run.v_metrics.ascent;
+    // Faux bold smears every glyph to the right by a few pixels.
+    let smear = (style.size / 24.0).round().max(1.0) as usize;
+    let height = run.v_metrics.ascent - run.v_metrics.descent;
+    let shader = Shader::new(&style.color, x as f32, y as f32, run.width, height);
+
+    for (index, glyph) in &run.glyphs {
+        let resolved = &run.fonts[*index];
+        let slant = if resolved.fake_italic {
+            ITALIC_SLANT
+        } else {
+            0.0
+        };
+
+        let p = glyph.position();
+        let (gx, gy) = (x as f32 + p.x, baseline + p.y);
+        let (fx, fy) = (gx.floor(), gy.floor());
+        let key = GlyphKey {
+            font: resolved.id,
+            size: style.size.to_bits(),
+            glyph: glyph.id(),
+            subpixel: (
+                ((gx - fx) * SUBPIXEL_STEPS) as u8,
+                ((gy - fy) * SUBPIXEL_STEPS) as u8,
+            ),
+            smear: if resolved.fake_bold { smear } else { 0 },
+        };
+        let Some(bitmap) = rasterize(glyph, key) else {
+            continue;
+        };
+
+        let (ox, oy) = (fx as i32 + bitmap.left, fy as i32 + bitmap.top);
+        for row in 0..bitmap.height {
+            let py = oy + row as i32;
+            let shift = ((baseline - py as f32) * slant).round() as i32;
+            let line = &bitmap.coverage[row * bitmap.width..(row + 1) * bitmap.width];
+            for (col, &v) in line.iter().enumerate() {
+                if v > 0 {
+                    surface.blend_shader(ox + col as i32 + shift, py, &shader, v as f32 / 255.0);
+                }
+            }
+        }
+    }
+
+    let thickness = (style.size / 16.0).round().max(1.0) as i32;
+    let (x0, x1) = (x, (x as f32 + run.width).round() as i32);
+    if style.underline {
+        let top = (baseline + style.size * 0.1).round() as i32;
+        for py in top..top + thickness {
+            surface.shade_span(py, x0, x1, &shader);
+        }
+    }
+    if style.strikethrough {
+        let top = (baseline - style.size * 0.3).round() as i32;
+        for py in top..top + thickness {
+            surface.shade_span(py, x0, x1, &shader);
+        }
+    }
+}
+
+/// The coverage bitmap for `key`, rendering and caching it on first use.
+/// `None` for glyphs without an outline, like spaces.
+fn rasterize(glyph: &PositionedGlyph<'static>, key: GlyphKey) -> Option<Arc<GlyphBitmap>> {
+    if let Some(bitmap) = GLYPHS.lock().unwrap().get(&key) {
+        return Some(bitmap).filter(|b| b.width > 0);
+    }
+
+    let offset = point(
+        key.subpixel.0 as f32 / SUBPIXEL_STEPS,
+        key.subpixel.1 as f32 / SUBPIXEL_STEPS,
+    );
+    let glyph = glyph.unpositioned().clone().positioned(offset);
+    let bitmap = match glyph.pixel_bounding_box() {
+        Some(bb) => {
+            let (w, h) = (bb.width() as usize + key.smear, bb.height() as usize);
+            let mut coverage = vec![0u8; w * h];
+            glyph.draw(|gx, gy, v| {
+                let start = gy as usize * w + gx as usize;
+                let v = (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+                for c in &mut coverage[start..=start + key.smear] {
+                    *c = (*c).max(v);
+                }
+            });
+            GlyphBitmap {
+                left: bb.min.x,
+                top: bb.min.y,
+                width: w,
+                height: h,
+                coverage,
+            }
+        }
+        None => GlyphBitmap {
+            left: 0,
+            top: 0,
+            width: 0,
+            height: 0,
+            coverage: Vec::new(),
+        },
+    };
+
+    let bitmap = Arc::new(bitmap);
+    GLYPHS.lock().unwrap().insert(key, bitmap.clone());
+    Some(bitmap).filter(|b| b.width > 0)
+}
@@ -1,16 +1,16 @@
-use crate::component;
+use crate::{color::Color, component, paint::Paint};
 
 #[derive(Debug, Clone)]
 pub struct RawTransform {
-    pub x: usize,
-    pub y: usize,
+    pub x: i32,
+    pub y: i32,
     pub width: usize,
     pub height: usize,
 }
 
 #[derive(Debug, Clone)]
 pub enum Position {
-    Const(usize),
+    Const(i32),
     Center,
     End,
 }
@@ -19,15 +19,27 @@ pub enum Position {
 pub enum Dimension {
     Auto,
     Const(usize),
+    /// A percentage of the parent size.
+    Percent(f32),
+    /// The space between the position and the far edge of the parent, less the margins.
+    Fill,
+    /// The inner dimension, but at least the given size.
+    Min(Box<Dimension>, usize),
+    /// The inner dimension, but at most the given size.
+    Max(Box<Dimension>, usize),
 }
 
 component!(Transform {
     pub x: Position,
     pub y: Position,
+    /// Signed offset added after layout, e.g. by animations.
     pub mx: i32,
     pub my: i32,
     pub width: Dimension,
     pub height: Dimension,
+    /// Space kept clear around the widget by positions, `Fill` sizes and the
+    /// layout containers.
+    pub margin: Sides<usize>,
 });
 
 impl Transform {
@@ -39,6 +51,7 @@ impl Transform {
             my: 0,
             width: Dimension::Auto,
             height: Dimension::Auto,
+            margin: Sides::default(),
         }
     }
 
@@ -50,10 +63,11 @@ impl Transform {
             my: 0,
             width: Dimension::Auto,
             height: Dimension::Auto,
+            margin: Sides::default(),
         }
     }
 
-    pub fn top(mut self, m: usize) -> Self {
+    pub fn top(mut self, m: i32) -> Self {
         self.y = Position::Const(m);
         self
     }
@@ -63,7 +77,17 @@ impl Transform {
         self
     }
 
-    pub fn margin(mut self, x: i32, y: i32) -> Self {
+    pub fn margins(mut self, margin: impl Into<Sides<usize>>) -> Self {
+        self.margin = margin.into();
+        self
+    }
+
+    #[deprecated(note = "use `offset`, or `margins` for space around the widget")]
+    pub fn margin(self, x: i32, y: i32) -> Self {
+        self.offset(x, y)
+    }
+
+    pub fn offset(mut self, x: i32, y: i32) -> Self {
         self.mx = x;
         self.my = y;
         self
@@ -81,16 +105,52 @@ impl Transform {
         self
     }
 
-    pub fn use_dimensions(&self, raw: &mut RawTransform) {
-        self.width.use_dimension(&mut raw.width);
-        self.height.use_dimension(&mut raw.height);
+    pub fn width(mut self, width: Dimension) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Dimension) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn use_dimensions(
+        &self,
+        parent_width: usize,
+        parent_height: usize,
+        raw: &mut RawTransform,
+    ) {
+        let m = &self.margin;
+        let x_offset = self.x.offset() + (m.left + m.right) as i32;
+        let y_offset = self.y.offset() + (m.top + m.bottom) as i32;
+        self.width
+            .use_dimension(parent_width, x_offset, &mut raw.width);
+        self.height
+            .use_dimension(parent_height, y_offset, &mut raw.height);
     }
 
+    /// Places the widget inside the parent minus its margins.
     pub fn use_position(&self, parent_width: usize, parent_height: usize, raw: &mut RawTransform) {
-        self.x
-            .use_position(raw.width, parent_width, self.mx, &mut raw.x);
-        self.y
-            .use_position(raw.height, parent_height, self.my, &mut raw.y);
+        let m = &self.margin;
+        self.x.use_position(
+            raw.width,
+            parent_width.saturating_sub(m.left + m.right),
+            self.mx + m.left as i32,
+            &mut raw.x,
+        );
+        self.y.use_position(
+            raw.height,
+            parent_height.saturating_sub(m.top + m.bottom),
+            self.my + m.top as i32,
+            &mut raw.y,
+        );
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -105,27 +165,397 @@ impl RawTransform {
     }
 }
 
+impl Default for RawTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Dimension {
-    pub fn use_dimension(&self, r: &mut usize) {
+    pub fn min(self, min: usize) -> Dimension {
+        Dimension::Min(Box::new(self), min)
+    }
+
+    pub fn max(self, max: usize) -> Dimension {
+        Dimension::Max(Box::new(self), max)
+    }
+
+    /// Whether the size comes from the content, ignoring min/max clamps.
+    pub fn is_auto(&self) -> bool {
+        match self {
+            Self::Auto => true,
+            Self::Min(d, _) | Self::Max(d, _) => d.is_auto(),
+            _ => false,
+        }
+    }
+
+    pub fn use_dimension(&self, parent: usize, offset: i32, r: &mut usize) {
         match self {
             Self::Auto => {}
             Self::Const(n) => *r = *n,
+            Self::Percent(p) => *r = (parent as f32 * p / 100.0).max(0.0).round() as usize,
+            Self::Fill => *r = parent.saturating_sub(offset.max(0) as usize),
+            Self::Min(d, min) => {
+                d.use_dimension(parent, offset, r);
+                *r = (*r).max(*min);
+            }
+            Self::Max(d, max) => {
+                d.use_dimension(parent, offset, r);
+                *r = (*r).min(*max);
+            }
         }
     }
 }
 
 impl Position {
-    pub fn use_position(&self, size: usize, parent: usize, m: i32, r: &mut usize) {
+    /// The offset from the parent's start edge known before layout.
+    pub fn offset(&self) -> i32 {
         match self {
-            Self::Center => *r = (parent - size) / 2,
-            Self::Const(n) => *r = *n,
-            Self::End => *r = parent - size,
+            Self::Const(n) => *n,
+            _ => 0,
+        }
+    }
+
+    /// Resolves the position; the result may be negative or past the parent
+    /// when the child is larger than its parent or pushed out by its margin.
+    pub fn use_position(&self, size: usize, parent: usize, m: i32, r: &mut i32) {
+        let free = parent as i64 - size as i64;
+        let pos = match self {
+            Self::Center => free.div_euclid(2),
+            Self::Const(n) => *n as i64,
+            Self::End => free,
+        };
+
+        *r = (pos + m as i64).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    }
+}
+
+/// One value for each side of a box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sides<T> {
+    pub top: T,
+    pub right: T,
+    pub bottom: T,
+    pub left: T,
+}
+
+impl<T: Copy> Sides<T> {
+    pub fn new(top: T, right: T, bottom: T, left: T) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    pub fn all(value: T) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    /// `vertical` for the top and bottom, `horizontal` for the left and right.
+    pub fn symmetric(vertical: T, horizontal: T) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+}
+
+impl<T: Copy> From<T> for Sides<T> {
+    fn from(value: T) -> Self {
+        Self::all(value)
+    }
+}
+
+/// The radius of each corner of a box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Corners {
+    pub top_left: usize,
+    pub top_right: usize,
+    pub bottom_right: usize,
+    pub bottom_left: usize,
+}
+
+impl Corners {
+    pub const fn new(
+        top_left: usize,
+        top_right: usize,
+        bottom_right: usize,
+        bottom_left: usize,
+    ) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    pub const fn all(radius: usize) -> Self {
+        Self::new(radius, radius, radius, radius)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The radii clockwise from the top left.
+    pub fn to_array(self) -> [usize; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
+
+    /// The radii of the edge `sides` further inside, keeping square corners square.
+    pub fn inset(self, sides: Sides<usize>) -> Self {
+        let shrink = |r: usize, a: usize, b: usize| {
+            if r == 0 {
+                0
+            } else {
+                r.saturating_sub(a.max(b))
+            }
+        };
+        Self::new(
+            shrink(self.top_left, sides.top, sides.left),
+            shrink(self.top_right, sides.top, sides.right),
+            shrink(self.bottom_right, sides.bottom, sides.right),
+            shrink(self.bottom_left, sides.bottom, sides.left),
+        )
+    }
+
+    /// The radii of the edge `by` further outside, keeping square corners square.
+    pub fn outset(self, by: usize) -> Self {
+        let grow = |r: usize| if r == 0 { 0 } else { r + by };
+        Self::new(
+            grow(self.top_left),
+            grow(self.top_right),
+            grow(self.bottom_right),
+            grow(self.bottom_left),
+        )
+    }
+}
+
+impl From<usize> for Corners {
+    fn from(radius: usize) -> Self {
+        Self::all(radius)
+    }
+}
+
+/// Lines drawn along the inside of a box's edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Border {
+    pub width: Sides<usize>,
+    pub color: Sides<Color>,
+}
+
+impl Border {
+    pub fn new(width: usize, color: impl Into<Color>) -> Self {
+        Self {
+            width: Sides::all(width),
+            color: Sides::all(color.into()),
         }
+    }
 
-        if m > 0 {
-            *r += m as usize;
-        } else {
-            *r -= -m as usize;
+    pub fn is_visible(&self) -> bool {
+        let Sides {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.width;
+        let c = self.color;
+        (top > 0 && !c.top.is_transparent())
+            || (right > 0 && !c.right.is_transparent())
+            || (bottom > 0 && !c.bottom.is_transparent())
+            || (left > 0 && !c.left.is_transparent())
+    }
+}
+
+/// A ring drawn outside a box, following its corners, such as a focus ring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Outline {
+    pub width: usize,
+    /// Gap between the box and the ring.
+    pub offset: usize,
+    pub color: Color,
+}
+
+impl Outline {
+    pub fn new(width: usize, color: impl Into<Color>) -> Self {
+        Self {
+            width,
+            offset: 0,
+            color: color.into(),
         }
     }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// A blurred copy of a box's shape drawn behind it, or inside it when `inset`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoxShadow {
+    pub offset: (i32, i32),
+    /// How far the edge fades out, like CSS's blur radius.
+    pub blur: usize,
+    /// Grows the shape before blurring; negative values shrink it.
+    pub spread: i32,
+    pub color: Color,
+    pub inset: bool,
+}
+
+impl BoxShadow {
+    pub fn new(x: i32, y: i32, blur: usize, color: impl Into<Color>) -> Self {
+        Self {
+            offset: (x, y),
+            blur,
+            spread: 0,
+            color: color.into(),
+            inset: false,
+        }
+    }
+
+    pub fn spread(mut self, spread: i32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn inset(mut self) -> Self {
+        self.inset = true;
+        self
+    }
+}
+
+/// Weight of a font face, 400 being regular and 700 bold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const LIGHT: Self = Self(300);
+    pub const NORMAL: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMI_BOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// How a run of text is drawn.
+///
+/// `family` names fonts registered with
+/// [`register_font`](crate::render::font::register_font). Bold and italic
+/// are synthesized when the family has no such face.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    /// Font size in pixels.
+    pub size: f32,
+    /// A solid color or a gradient spanning each drawn line.
+    pub color: Paint,
+    /// `None` uses the default family.
+    pub family: Option<String>,
+    pub weight: FontWeight,
+    pub italic: bool,
+    /// Extra space after every char, in pixels.
+    pub letter_spacing: f32,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+impl TextStyle {
+    pub fn new(size: f32, color: impl Into<Paint>) -> Self {
+        Self {
+            size,
+            color: color.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Copies the properties `inherit` selects from `parent`.
+    pub fn inherit(&mut self, parent: &TextStyle, inherit: Inherit) {
+        if inherit.color {
+            self.color = parent.color.clone();
+        }
+        if inherit.family {
+            self.family = parent.family.clone();
+        }
+        if inherit.size {
+            self.size = parent.size;
+        }
+        if inherit.weight {
+            self.weight = parent.weight;
+        }
+        if inherit.italic {
+            self.italic = parent.italic;
+        }
+        if inherit.underline {
+            self.underline = parent.underline;
+        }
+        if inherit.strikethrough {
+            self.strikethrough = parent.strikethrough;
+        }
+    }
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            size: 24.0,
+            color: Color::WHITE.into(),
+            family: None,
+            weight: FontWeight::NORMAL,
+            italic: false,
+            letter_spacing: 0.0,
+            underline: false,
+            strikethrough: false,
+        }
+    }
+}
+
+/// Which text properties an element takes from its parent instead of its own style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inherit {
+    pub color: bool,
+    pub family: bool,
+    pub size: bool,
+    pub weight: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+impl Inherit {
+    pub const ALL: Self = Self {
+        color: true,
+        family: true,
+        size: true,
+        weight: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+    };
+    pub const NONE: Self = Self {
+        color: false,
+        family: false,
+        size: false,
+        weight: false,
+        italic: false,
+        underline: false,
+        strikethrough: false,
+    };
+}
+
+impl Default for Inherit {
+    fn default() -> Self {
+        Self::ALL
+    }
 }
@@ -0,0 +1,229 @@
+//! Helpers for pixel-level regression tests.
+//!
+//! Render a [`Screen`] or a single [`Element`] into a [`Snapshot`] and compare it
+//! against a golden PNG with [`assert_snapshot`]. Set `OSGUI_UPDATE_SNAPSHOTS=1`
+//! to write new goldens or overwrite existing ones; otherwise a missing golden fails.
+//!
+//! Only built for the crate's own tests or with the `testing` feature.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use crate::{backend::Backend, style::Transform, widget::Element, Screen};
+
+/// Set to anything but `0` to write goldens instead of comparing against them.
+pub const UPDATE_ENV: &str = "OSGUI_UPDATE_SNAPSHOTS";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotDiff {
+    pub width: usize,
+    pub height: usize,
+    pub mismatched: usize,
+    pub max_delta: u8,
+    pub first_mismatch: Option<(usize, usize)>,
+    /// Mismatching pixels in red over a dimmed copy of the expected image.
+    pub image: Vec<u32>,
+}
+
+/// Renders `element` alone on a headless screen of the given size.
+pub fn render_element<E: Element + 'static>(
+    element: E,
+    transform: Transform,
+    width: usize,
+    height: usize,
+) -> Snapshot {
+    let mut screen = Screen::headless(width, height);
+    screen.draw(element).component(transform);
+    render_screen(&mut screen).expect("headless backend never fails to present")
+}
+
+/// How many glyph bitmaps have been rendered so far, as opposed to taken
+/// from the glyph cache. Shared by every screen in the process.
+pub fn glyphs_rasterized() -> usize {
+    crate::render::glyphs_rasterized()
+}
+
+/// Steps `screen` by one frame and captures the result.
+pub fn render_screen<B: Backend>(screen: &mut Screen<B>) -> std::io::Result<Snapshot> {
+    screen.frame()?;
+    let (width, height) = screen.backend().get_size();
+    Ok(Snapshot {
+        width,
+        height,
+        pixels: screen.get_buffer().to_vec(),
+    })
+}
+
+/// Compares `actual` against the golden PNG at `path`.
+///
+/// A missing golden fails unless [`UPDATE_ENV`] is set. On mismatch,
+/// `<path>.actual.png` and `<path>.diff.png` are written next to the golden
+/// and the test panics with a summary of the difference.
+#[track_caller]
+pub fn assert_snapshot<P: AsRef<Path>>(path: P, actual: &Snapshot, tolerance: u8) {
+    let path = path.as_ref();
+    let update = std::env::var_os(UPDATE_ENV).is_some_and(|v| v != "0");
+
+    if update {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        actual
+            .save_png(path)
+            .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+        return;
+    }
+    if !path.exists() {
+        panic!(
+            "snapshot {} is missing; run with {}=1 to write it",
+            path.display(),
+            UPDATE_ENV,
+        );
+    }
+
+    let expected = Snapshot::load_png(path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+
+    if let Some(diff) = expected.diff(actual, tolerance) {
+        let actual_path = sibling(path, "actual");
+        let diff_path = sibling(path, "diff");
+        actual.save_png(&actual_path).unwrap();
+        Snapshot {
+            width: diff.width,
+            height: diff.height,
+            pixels: diff.image.clone(),
+        }
+        .save_png(&diff_path)
+        .unwrap();
+
+        panic!(
+            "snapshot {} differs: {} pixel(s) over tolerance {} (max delta {}, first at {:?}); see {} and {}",
+            path.display(),
+            diff.mismatched,
+            tolerance,
+            diff.max_delta,
+            diff.first_mismatch,
+            actual_path.display(),
+            diff_path.display(),
+        );
+    }
+}
+
+impl Snapshot {
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<u32> {
+        if x < self.width && y < self.height {
+            self.pixels.get(y * self.width + x).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `None` when every channel of every pixel is within `tolerance`.
+    pub fn diff(&self, other: &Snapshot, tolerance: u8) -> Option<SnapshotDiff> {
+        let width = self.width.max(other.width);
+        let height = self.height.max(other.height);
+        let mut diff = SnapshotDiff {
+            width,
+            height,
+            mismatched: 0,
+            max_delta: 0,
+            first_mismatch: None,
+            image: vec![0; width * height],
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                let a = self.get_pixel(x, y);
+                let b = other.get_pixel(x, y);
+                let delta = match (a, b) {
+                    (Some(a), Some(b)) => channel_delta(a, b),
+                    _ => u8::MAX,
+                };
+
+                diff.max_delta = diff.max_delta.max(delta);
+                diff.image[y * width + x] = if delta > tolerance {
+                    diff.mismatched += 1;
+                    diff.first_mismatch.get_or_insert((x, y));
+                    0xFF0000
+                } else {
+                    dim(a.or(b).unwrap_or(0))
+                };
+            }
+        }
+
+        if diff.mismatched > 0 {
+            Some(diff)
+        } else {
+            None
+        }
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut data = Vec::with_capacity(self.pixels.len() * 3);
+        for p in &self.pixels {
+            data.extend_from_slice(&[(p >> 16) as u8, (p >> 8) as u8, *p as u8]);
+        }
+
+        encoder
+            .write_header()
+            .and_then(|mut w| w.write_image_data(&data))
+            .map_err(std::io::Error::other)
+    }
+
+    pub fn load_png<P: AsRef<Path>>(path: P) -> std::io::Result<Snapshot> {
+        let mut decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(std::io::Error::other)?;
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut data)
+            .map_err(std::io::Error::other)?;
+
+        let channels = info.color_type.samples();
+        let pixels = data[..info.buffer_size()]
+            .chunks(channels)
+            .map(|c| match c.len() {
+                1 | 2 => u32::from_be_bytes([0, c[0], c[0], c[0]]),
+                _ => u32::from_be_bytes([0, c[0], c[1], c[2]]),
+            })
+            .collect();
+
+        Ok(Snapshot {
+            width: info.width as usize,
+            height: info.height as usize,
+            pixels,
+        })
+    }
+}
+
+fn channel_delta(a: u32, b: u32) -> u8 {
+    [0, 8, 16]
+        .iter()
+        .map(|s| ((a >> s) as u8).abs_diff((b >> s) as u8))
+        .max()
+        .unwrap_or(0)
+}
+
+fn dim(p: u32) -> u32 {
+    (p >> 2) & 0x3F3F3F
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}.{}.png", stem, suffix))
+}
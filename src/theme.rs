@@ -0,0 +1,261 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    component,
+    elements::div::DivStyle,
+    paint::Paint,
+    style::{Border, BoxShadow, Corners, FontWeight, Outline, Sides, TextStyle},
+};
+
+component!(
+    /// Space separated names of classes in the screen's [`Theme`], applied in order.
+    Class(pub &'static str)
+);
+
+/// The properties a class sets; `None` leaves them to the element.
+///
+/// Text properties pass from a widget to its descendants, unless they set
+/// their own. Box properties only fill in what a [`DivStyle`] leaves `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Style {
+    pub color: Option<Paint>,
+    pub family: Option<String>,
+    pub size: Option<f32>,
+    pub weight: Option<FontWeight>,
+    pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub background: Option<Paint>,
+    pub corner_radius: Option<Corners>,
+    pub border: Option<Border>,
+    pub padding: Option<Sides<usize>>,
+    pub outline: Option<Outline>,
+    /// Empty leaves the element's shadows.
+    pub box_shadow: Vec<BoxShadow>,
+    pub backdrop_blur: Option<usize>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn color(mut self, color: impl Into<Paint>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn family(mut self, family: &str) -> Self {
+        self.family = Some(family.to_string());
+        self
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.underline = Some(underline);
+        self
+    }
+
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = Some(strikethrough);
+        self
+    }
+
+    pub fn background(mut self, background: impl Into<Paint>) -> Self {
+        self.background = Some(background.into());
+        self
+    }
+
+    pub fn corner_radius(mut self, radius: impl Into<Corners>) -> Self {
+        self.corner_radius = Some(radius.into());
+        self
+    }
+
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    pub fn padding(mut self, padding: impl Into<Sides<usize>>) -> Self {
+        self.padding = Some(padding.into());
+        self
+    }
+
+    pub fn outline(mut self, outline: Outline) -> Self {
+        self.outline = Some(outline);
+        self
+    }
+
+    /// Adds a shadow on top of the ones already set.
+    pub fn box_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.box_shadow.push(shadow);
+        self
+    }
+
+    pub fn backdrop_blur(mut self, radius: usize) -> Self {
+        self.backdrop_blur = Some(radius);
+        self
+    }
+
+    /// Overrides the properties `other` sets.
+    pub fn merge(&mut self, other: &Style) {
+        fn set<T: Clone>(value: &mut Option<T>, other: &Option<T>) {
+            if other.is_some() {
+                value.clone_from(other);
+            }
+        }
+        set(&mut self.color, &other.color);
+        set(&mut self.family, &other.family);
+        set(&mut self.size, &other.size);
+        set(&mut self.weight, &other.weight);
+        set(&mut self.italic, &other.italic);
+        set(&mut self.underline, &other.underline);
+        set(&mut self.strikethrough, &other.strikethrough);
+        set(&mut self.background, &other.background);
+        set(&mut self.corner_radius, &other.corner_radius);
+        set(&mut self.border, &other.border);
+        set(&mut self.padding, &other.padding);
+        set(&mut self.outline, &other.outline);
+        if !other.box_shadow.is_empty() {
+            self.box_shadow.clone_from(&other.box_shadow);
+        }
+        set(&mut self.backdrop_blur, &other.backdrop_blur);
+    }
+
+    /// Writes the text properties this style sets into `text`.
+    pub fn apply_text(&self, text: &mut TextStyle) {
+        if let Some(color) = &self.color {
+            text.color = color.clone();
+        }
+        if let Some(family) = &self.family {
+            text.family = Some(family.clone());
+        }
+        if let Some(size) = self.size {
+            text.size = size;
+        }
+        if let Some(weight) = self.weight {
+            text.weight = weight;
+        }
+        if let Some(italic) = self.italic {
+            text.italic = italic;
+        }
+        if let Some(underline) = self.underline {
+            text.underline = underline;
+        }
+        if let Some(strikethrough) = self.strikethrough {
+            text.strikethrough = strikethrough;
+        }
+    }
+
+    /// `div` with the fields it leaves unset filled in from this style.
+    pub fn fill_div<'a>(&self, div: &'a DivStyle) -> Cow<'a, DivStyle> {
+        fn fills<T>(value: &Option<T>, class: &Option<T>) -> bool {
+            value.is_none() && class.is_some()
+        }
+        fn fill<T: Clone>(value: &mut Option<T>, class: &Option<T>) {
+            if value.is_none() {
+                value.clone_from(class);
+            }
+        }
+
+        let shadows = (!self.box_shadow.is_empty()).then(|| self.box_shadow.clone());
+        if !(fills(&div.background_color, &self.background)
+            || fills(&div.corner_radius, &self.corner_radius)
+            || fills(&div.border, &self.border)
+            || fills(&div.padding, &self.padding)
+            || fills(&div.outline, &self.outline)
+            || fills(&div.box_shadow, &shadows)
+            || fills(&div.backdrop_blur, &self.backdrop_blur))
+        {
+            return Cow::Borrowed(div);
+        }
+
+        let mut div = div.clone();
+        fill(&mut div.background_color, &self.background);
+        fill(&mut div.corner_radius, &self.corner_radius);
+        fill(&mut div.border, &self.border);
+        fill(&mut div.padding, &self.padding);
+        fill(&mut div.outline, &self.outline);
+        fill(&mut div.box_shadow, &shadows);
+        fill(&mut div.backdrop_blur, &self.backdrop_blur);
+        Cow::Owned(div)
+    }
+}
+
+/// Named [`Style`]s that widgets opt into with a [`Class`] component.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    /// Inherited by every top-level widget; its background fills the screen.
+    pub root: Style,
+    pub classes: HashMap<String, Style>,
+}
+
+impl Theme {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn root(mut self, style: Style) -> Self {
+        self.root = style;
+        self
+    }
+
+    pub fn class(mut self, name: &str, style: Style) -> Self {
+        self.classes.insert(name.to_string(), style);
+        self
+    }
+
+    /// The combined style of the space separated class `names`, later ones
+    /// winning. Unknown names are skipped.
+    pub fn resolve(&self, names: &str) -> Style {
+        let mut style = Style::default();
+        for class in names.split_whitespace().filter_map(|n| self.classes.get(n)) {
+            style.merge(class);
+        }
+        style
+    }
+
+    /// The text style top-level widgets inherit.
+    pub fn root_text(&self) -> TextStyle {
+        let mut text = TextStyle::default();
+        self.root.apply_text(&mut text);
+        text
+    }
+}
+
+/// The theme a [`Screen`](crate::Screen) renders with. Clones share it, so a
+/// theme set through any of them restyles the whole tree on the next frame.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeHandle(Arc<Mutex<Arc<Theme>>>);
+
+impl ThemeHandle {
+    pub fn set(&self, theme: Theme) {
+        *self.0.lock().unwrap() = Arc::new(theme);
+    }
+
+    pub fn get(&self) -> Arc<Theme> {
+        self.0.lock().unwrap().clone()
+    }
+}
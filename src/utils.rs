@@ -1,24 +1,95 @@
 use rusttype::{point, Font, Scale};
 
+use crate::{render::font, style::TextStyle};
+
+/// Size of `text` drawn at `scale`, with every `\n` starting a new line.
 pub fn measure_text(font: &Font, text: &str, scale: Scale) -> (u32, u32) {
     let v_metrics = font.v_metrics(scale);
     let ascent = v_metrics.ascent;
     let descent = v_metrics.descent;
-    let height = (ascent - descent).ceil() as u32;
+    let line_height = (ascent - descent).ceil() as u32;
 
-    let width = font
-        .layout(text, scale, point(0.0, 0.0))
-        .last()
-        .map(|g| {
-            let bb = g.pixel_bounding_box();
-            if let Some(bb) = bb {
-                bb.max.x as f32
-            } else {
-                g.position().x
-            }
+    let mut lines = 0;
+    let width = text
+        .split('\n')
+        .map(|line| {
+            lines += 1;
+            font.layout(line, scale, point(0.0, 0.0))
+                .last()
+                .map(|g| {
+                    let bb = g.pixel_bounding_box();
+                    if let Some(bb) = bb {
+                        bb.max.x as f32
+                    } else {
+                        g.position().x
+                    }
+                })
+                .unwrap_or(0.0)
+                .ceil() as u32
         })
-        .unwrap_or(0.0)
-        .ceil() as u32;
+        .max()
+        .unwrap_or(0);
+
+    (width, line_height * lines)
+}
+
+/// The x offset of every char boundary in `text`, including the end, so
+/// `result[i]` is where a caret before the `i`th char is drawn.
+pub fn caret_positions(font: &Font, text: &str, scale: Scale) -> Vec<f32> {
+    let mut out = Vec::with_capacity(text.chars().count() + 1);
+    let mut end = 0.0;
+    for g in font.layout(text, scale, point(0.0, 0.0)) {
+        out.push(g.position().x);
+        end = g.position().x + g.unpositioned().h_metrics().advance_width;
+    }
+    out.push(end);
+    out
+}
+
+/// [`caret_positions`] for text drawn with `style`, including its font
+/// fallbacks and letter spacing.
+pub fn styled_caret_positions(text: &str, style: &TextStyle) -> Vec<f32> {
+    let run = font::layout(text, style);
+    run.glyphs
+        .iter()
+        .map(|(_, g)| g.position().x)
+        .chain(std::iter::once(run.width))
+        .collect()
+}
 
-    (width, height)
+/// Char indices where the wrapped rows of a line start, the first always 0.
+///
+/// `carets` comes from [`caret_positions`]. With `words`, a row breaks after
+/// the last whitespace that fits in `width` and only splits a word wider
+/// than a whole row; whitespace may hang past the edge. A `width` of 0 or
+/// less disables wrapping.
+pub fn wrap_rows(text: &str, carets: &[f32], width: f32, words: bool) -> Vec<usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut rows = vec![0];
+    let mut start = 0;
+    while width > 0.0 && start < chars.len() {
+        let mut end = start + 1;
+        while end < chars.len() && carets[end + 1] - carets[start] <= width {
+            end += 1;
+        }
+        if words {
+            while end < chars.len() && chars[end].is_whitespace() {
+                end += 1;
+            }
+        }
+        if end == chars.len() {
+            break;
+        }
+        if words && !chars[end - 1].is_whitespace() {
+            if let Some(k) = (start + 1..end)
+                .rev()
+                .find(|&k| chars[k - 1].is_whitespace())
+            {
+                end = k;
+            }
+        }
+        rows.push(end);
+        start = end;
+    }
+    rows
 }
@@ -1,10 +1,11 @@
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
+    fmt,
     sync::{Arc, Mutex},
 };
 
-use crate::render::RenderScope;
+use crate::{events::Event, render::RenderScope};
 
 pub type BoxedElement = Box<dyn Element + Send + Sync>;
 pub type BoxedComponent = Box<dyn Component + Send + Sync>;
@@ -12,6 +13,26 @@ pub type BoxedComponent = Box<dyn Component + Send + Sync>;
 pub trait Element: Send + Sync {
     #[allow(unused)]
     fn render(&mut self, scope: &mut RenderScope) {}
+    /// The size the element takes in `scope`, which is set up as for
+    /// [`Self::render`], without drawing. Layout containers call this before
+    /// placing children, so it must not change state that rendering advances.
+    /// The default renders into `scope` and reads its size, which suits
+    /// elements that only draw.
+    fn measure(&mut self, scope: &mut RenderScope) -> (usize, usize) {
+        self.render(scope);
+        scope.get_max_size()
+    }
+    /// Child widgets in draw order, used to walk the widget tree.
+    fn children(&self) -> Vec<Arc<Widget>> {
+        Vec::new()
+    }
+    /// Whether the element takes keyboard focus on its own.
+    fn focusable(&self) -> bool {
+        false
+    }
+    /// Called with the element already locked, so `widget` may only be used for components.
+    #[allow(unused)]
+    fn event(&mut self, widget: &Arc<Widget>, event: &dyn Event) {}
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
@@ -45,12 +66,37 @@ impl Widget {
         self
     }
 
+    pub fn remove_component<C: Component + 'static>(self: &Arc<Self>) -> &Arc<Self> {
+        self.1.lock().unwrap().remove(&TypeId::of::<C>());
+        self
+    }
+
+    pub fn has<C: Component + 'static>(&self) -> bool {
+        self.1.lock().unwrap().contains_key(&TypeId::of::<C>())
+    }
+
+    /// This widget followed by all of its descendants, depth first.
+    pub fn walk(self: &Arc<Self>) -> Vec<Arc<Widget>> {
+        let mut out = vec![self.clone()];
+        let children = self.0.lock().unwrap().children();
+        for child in &children {
+            out.extend(child.walk());
+        }
+        out
+    }
+
     pub fn get<C: Component + 'static + Clone>(&self) -> Option<C> {
         self.1
             .lock()
             .unwrap()
             .get(&TypeId::of::<C>())
             .and_then(|c| c.as_any().downcast_ref::<C>())
-            .map(|c| c.clone())
+            .cloned()
+    }
+}
+
+impl fmt::Debug for Widget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Widget").finish_non_exhaustive()
     }
 }
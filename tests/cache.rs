@@ -0,0 +1,58 @@
+//! The glyph and layout caches are shared by the whole process, so these tests
+//! live apart from the ones that draw other text.
+
+use std::sync::Arc;
+
+use osgui::{
+    elements::text::Text,
+    render::font,
+    style::{TextStyle, Transform},
+    testing::{glyphs_rasterized, render_element},
+};
+
+const GLYPHS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+fn draw(size: f32) {
+    render_element(Text::new(GLYPHS).size(size), Transform::new(), 400, 80);
+}
+
+#[test]
+fn glyph_cache_keeps_recent_glyphs() {
+    draw(12.0);
+    let before = glyphs_rasterized();
+    draw(12.0);
+    assert_eq!(
+        glyphs_rasterized(),
+        before,
+        "drawing the same text again missed"
+    );
+
+    // Far more new glyphs than the cache holds, while the 12px ones stay in use.
+    let sizes: Vec<f32> = (0..200).map(|i| 13.0 + i as f32 * 0.25).collect();
+    for &size in &sizes {
+        draw(size);
+        let drawn = glyphs_rasterized();
+        draw(12.0);
+        assert_eq!(glyphs_rasterized(), drawn, "glyphs in use were evicted");
+    }
+    assert!(glyphs_rasterized() - before > 8192);
+
+    let before = glyphs_rasterized();
+    draw(sizes[0]);
+    assert!(glyphs_rasterized() > before, "the oldest glyphs were kept");
+}
+
+#[test]
+fn layout_cache_keeps_recent_layouts() {
+    let style = TextStyle::default();
+    let kept = font::layout("kept", &style);
+    assert!(Arc::ptr_eq(&kept, &font::layout("kept", &style)));
+
+    let first = font::layout("text 0", &style);
+    for i in 1..6000 {
+        font::layout(&format!("text {}", i), &style);
+        font::layout("kept", &style);
+    }
+    assert!(Arc::ptr_eq(&kept, &font::layout("kept", &style)));
+    assert!(!Arc::ptr_eq(&first, &font::layout("text 0", &style)));
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::{at, pixel};
+use osgui::{
+    color::Color,
+    elements::{
+        custom,
+        div::{Div, DivStyle},
+        text::Text,
+        Rect,
+    },
+    Screen,
+};
+
+#[test]
+fn source_over() {
+    // Half red over blue rounds each channel to the nearest value.
+    assert_eq!(Color::rgba(255, 0, 0, 128).blend(0x0000FF, 1.0), 0x80007F);
+    assert_eq!(Color::rgb(0x123456).blend(0xFFFFFF, 1.0), 0x123456);
+    assert_eq!(Color::TRANSPARENT.blend(0xABCDEF, 1.0), 0xABCDEF);
+
+    // Coverage scales the source alpha, as for anti-aliased edges.
+    assert_eq!(Color::WHITE.blend(0x000000, 0.5), 0x808080);
+    assert_eq!(Color::WHITE.blend(0x336699, 0.0), 0x336699);
+    assert_eq!(
+        Color::rgba(255, 255, 255, 128).blend(0x000000, 0.5),
+        0x404040
+    );
+}
+
+#[test]
+fn translucent_shapes_and_scopes() {
+    let mut screen = Screen::headless(60, 20);
+    screen
+        .draw(Rect(Color::rgba(255, 255, 255, 64).into(), 0))
+        .component(at(0, 0, 20, 20));
+    screen
+        .draw(Rect(Color::WHITE.into(), 0))
+        .component(at(20, 0, 40, 20));
+    // A div draws into its own scope, which is blended when merged.
+    screen
+        .draw(Div {
+            style: DivStyle::new().background(Color::rgba(0, 0, 255, 128)),
+            ..Default::default()
+        })
+        .component(at(40, 0, 20, 20));
+    screen.frame().unwrap();
+
+    assert_eq!(pixel(&screen, 10, 10), 0x404040);
+    assert_eq!(pixel(&screen, 30, 10), 0xFFFFFF);
+    assert_eq!(pixel(&screen, 50, 10), 0x7F7FFF);
+}
+
+#[test]
+fn zero_is_black() {
+    let mut screen = Screen::headless(120, 40);
+    screen
+        .draw(Rect(Color::WHITE.into(), 0))
+        .component(at(0, 0, 120, 40));
+    screen
+        .draw(custom(|scope| scope.draw_rect(0, 0, 20, 20, 0)))
+        .component(at(0, 0, 20, 20));
+    screen
+        .draw(custom(|scope| scope.draw_rect_rounded(0, 0, 20, 20, 4, 0)))
+        .component(at(20, 0, 20, 20));
+    screen
+        .draw(Text::new("Ink").size(24.0).color(0))
+        .component(at(40, 0, 80, 40));
+    // Without a background, a div leaves what is behind it alone.
+    screen.draw(Div::default()).component(at(0, 20, 40, 20));
+    screen.frame().unwrap();
+
+    assert_eq!(pixel(&screen, 10, 10), 0x000000);
+    assert_eq!(pixel(&screen, 30, 10), 0x000000);
+    let text_ink = (40..120)
+        .flat_map(|x| (0..40).map(move |y| (x, y)))
+        .filter(|&(x, y)| pixel(&screen, x, y) == 0x000000)
+        .count();
+    assert!(text_ink > 20, "only {} black text pixels", text_ink);
+    assert_eq!(pixel(&screen, 20, 30), 0xFFFFFF);
+}
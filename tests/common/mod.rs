@@ -0,0 +1,63 @@
+//! Helpers shared by the snapshot tests.
+#![allow(dead_code)]
+
+use std::{path::PathBuf, sync::Arc};
+
+use osgui::{
+    backend::{Backend, HeadlessBackend},
+    events::Event,
+    render::Layout,
+    style::{Position, Transform},
+    testing::{assert_snapshot, render_screen},
+    widget::Widget,
+    Screen,
+};
+
+/// Channel difference allowed before a pixel counts as changed.
+pub const TOLERANCE: u8 = 1;
+
+pub fn golden(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.png", name))
+}
+
+/// A fixed size at a fixed position.
+pub fn at(x: i32, y: i32, width: usize, height: usize) -> Transform {
+    Transform::new()
+        .pos(Position::Const(x), Position::Const(y))
+        .dimensions(width, height)
+}
+
+/// Renders one frame of `screen` and compares it with the golden `name`.
+#[track_caller]
+pub fn check(name: &str, screen: &mut Screen<HeadlessBackend>) {
+    let snapshot = render_screen(screen).expect("headless backend never fails to present");
+    assert_snapshot(golden(name), &snapshot, TOLERANCE);
+}
+
+/// Delivers `event` straight to `widget`, as an extension would.
+pub fn send(widget: &Arc<Widget>, event: &dyn Event) {
+    widget.0.lock().unwrap().event(widget, event);
+}
+
+/// Runs `f` on the element of `widget`, which must be a `T`.
+pub fn element<T: 'static, R>(widget: &Arc<Widget>, f: impl FnOnce(&mut T) -> R) -> R {
+    let mut element = widget.0.lock().unwrap();
+    f(element
+        .as_any_mut()
+        .downcast_mut()
+        .expect("widget holds another element"))
+}
+
+/// Where `widget` was drawn in the last frame, as `(x, y, width, height)`.
+pub fn bounds(widget: &Arc<Widget>) -> (i32, i32, usize, usize) {
+    let layout = widget.get::<Layout>().expect("widget was not drawn");
+    (layout.x, layout.y, layout.width, layout.height)
+}
+
+/// The `0xRRGGBB` pixel at `(x, y)` of the last frame.
+pub fn pixel(screen: &Screen<HeadlessBackend>, x: usize, y: usize) -> u32 {
+    let (width, _) = screen.backend().get_size();
+    screen.get_buffer()[y * width + x] & 0xFFFFFF
+}
@@ -0,0 +1,323 @@
+mod common;
+
+use std::{f32::consts::PI, sync::Arc};
+
+use common::{at, check, golden, pixel};
+use osgui::{
+    color::Color,
+    elements::{
+        custom,
+        div::{Div, DivStyle},
+        image::{Fit, Image},
+        text::Text,
+        Rect,
+    },
+    image::{self, Filter, ImageData},
+    paint::Paint,
+    render::path::{FillRule, Path},
+    style::{Border, Corners, Outline, Sides},
+    Screen,
+};
+
+/// A 4×3 image of colored cells, wider than tall so fits differ.
+fn cells() -> Arc<ImageData> {
+    let colors = [
+        0xFFE04040, 0xFF40A040, 0xFF4060E0, 0xFFE0A020, 0xFFFFFFFF, 0xFF000000,
+    ];
+    let pixels = (0..12)
+        .map(|i| colors[(i + i / 4) % colors.len()])
+        .collect();
+    Arc::new(ImageData::new(4, 3, pixels))
+}
+
+/// A 2×2, 24 bit BMP: red and green over blue and white.
+fn bmp() -> Vec<u8> {
+    let mut bytes = b"BM".to_vec();
+    for value in [70u32, 0, 54, 40, 2, 2] {
+        bytes.extend(value.to_le_bytes());
+    }
+    bytes.extend(1u16.to_le_bytes());
+    bytes.extend(24u16.to_le_bytes());
+    bytes.extend([0; 24]);
+    // Rows run bottom-up in BGR, each padded to 4 bytes.
+    bytes.extend([0xFF, 0, 0, 0xFF, 0xFF, 0xFF, 0, 0]);
+    bytes.extend([0, 0, 0xFF, 0, 0xFF, 0, 0, 0]);
+    bytes
+}
+
+/// Checks each channel of two `0xRRGGBB` colors is within a few steps.
+#[track_caller]
+fn assert_close(actual: u32, expected: u32) {
+    let near = (0..3).all(|i| {
+        let channel = |c: u32| (c >> (i * 8) & 0xFF) as i32;
+        (channel(actual) - channel(expected)).abs() <= 4
+    });
+    assert!(near, "{:06X} is not close to {:06X}", actual, expected);
+}
+
+#[test]
+fn image() {
+    let mut screen = Screen::headless(240, 120);
+    screen
+        .draw(Rect(Color::rgb(0x303030).into(), 0))
+        .component(at(0, 0, 240, 120));
+
+    let images = [
+        Image::new(cells()).fit(Fit::Fill).filter(Filter::Nearest),
+        Image::new(cells()).fit(Fit::Fill),
+        Image::new(cells())
+            .fit(Fit::Contain)
+            .filter(Filter::Nearest),
+        Image::new(cells()).fit(Fit::Cover).filter(Filter::Nearest),
+    ];
+    for (i, image) in images.into_iter().enumerate() {
+        screen
+            .draw(image)
+            .component(at(4 + i as i32 * 59, 4, 55, 112));
+    }
+
+    check("image", &mut screen);
+
+    // `Fill` stretches the cells to the box, corner to corner.
+    assert_eq!(pixel(&screen, 10, 10), 0xE04040);
+    assert_eq!(pixel(&screen, 54, 10), 0xE0A020);
+    assert_eq!(pixel(&screen, 10, 110), 0xFFFFFF);
+    // `Contain` is 41 tall and centered, leaving the background above and below.
+    assert_eq!(pixel(&screen, 130, 10), 0x303030);
+    assert_eq!(pixel(&screen, 130, 45), 0xE04040);
+    assert_eq!(pixel(&screen, 130, 110), 0x303030);
+    // `Cover` is 149 wide, so only the middle two columns show, clipped to the box.
+    assert_eq!(pixel(&screen, 179, 10), 0x303030);
+    assert_eq!(pixel(&screen, 190, 10), 0x40A040);
+    assert_eq!(pixel(&screen, 230, 10), 0x4060E0);
+}
+
+#[test]
+fn paths() {
+    let mut screen = Screen::headless(240, 120);
+    screen.draw(custom(|scope| {
+        let star: Vec<(f32, f32)> = (0..5)
+            .map(|i| {
+                let angle = -PI / 2.0 + i as f32 * 4.0 * PI / 5.0;
+                (40.0 + 34.0 * angle.cos(), 60.0 + 34.0 * angle.sin())
+            })
+            .collect();
+        scope.fill_path(
+            &Path::polyline(&star, true),
+            FillRule::NonZero,
+            Color::rgb(0xE0A020),
+        );
+        let star = star.iter().map(|&(x, y)| (x + 80.0, y)).collect::<Vec<_>>();
+        scope.fill_path(
+            &Path::polyline(&star, true),
+            FillRule::EvenOdd,
+            Color::rgb(0xE0A020),
+        );
+
+        let wave = Path::new()
+            .move_to(164.0, 90.0)
+            .cubic_to(180.0, 20.0, 200.0, 120.0, 230.0, 30.0);
+        scope.stroke_path(&wave, 3.0, Color::rgb(0x4080F0));
+        scope.draw_arc(200.0, 70.0, 24.0, 0.0, 1.5 * PI, 4.0, Color::rgb(0xE04040));
+        scope.stroke_circle(200.0, 70.0, 10.0, 1.5, Color::WHITE);
+        scope.draw_line(160.0, 110.0, 236.0, 104.0, 2.0, Color::rgb(0x40A040));
+    }));
+
+    check("paths", &mut screen);
+
+    // Non-zero fills the star's middle and even-odd leaves it open.
+    let gold = 0xE0A020;
+    assert_eq!(pixel(&screen, 40, 60), gold);
+    assert_eq!(pixel(&screen, 120, 60), 0x000000);
+    assert_eq!(pixel(&screen, 40, 32), gold);
+    assert_eq!(pixel(&screen, 120, 32), gold);
+
+    // The arc turns clockwise from 3 o'clock, so it passes 6 and 9 and stops at 12.
+    for (x, y) in [(216, 86), (182, 86), (182, 52)] {
+        assert_eq!(pixel(&screen, x, y), 0xE04040, "arc at {}, {}", x, y);
+    }
+    assert_eq!(pixel(&screen, 216, 52), 0x000000);
+    // The thin white ring only partly covers the pixels along it.
+    let ring = pixel(&screen, 210, 70);
+    let (r, g, b) = (ring >> 16, ring >> 8 & 0xFF, ring & 0xFF);
+    assert!(
+        r == g && g == b && r > 0x80 && r < 0xFF,
+        "ring {:06X}",
+        ring
+    );
+
+    // The line is 2 wide at y 108 around x 180, with partly covered rows either side.
+    let column: Vec<u32> = (106..111).map(|y| pixel(&screen, 180, y)).collect();
+    assert_eq!(column[2], 0x40A040);
+    for edge in [column[1], column[3]] {
+        let green = edge >> 8 & 0xFF;
+        assert!(green > 0 && green < 0xA0, "edge {:06X}", edge);
+    }
+    assert_eq!((column[0], column[4]), (0, 0));
+}
+
+#[test]
+fn gradients() {
+    let mut screen = Screen::headless(240, 120);
+
+    let stops = [(0.0, Color::rgb(0xE04040)), (1.0, Color::rgb(0x4060E0))];
+    screen
+        .draw(Rect(Paint::linear(90.0, stops), 0))
+        .component(at(4, 4, 72, 52));
+    screen
+        .draw(Rect(Paint::linear(135.0, stops), 12))
+        .component(at(84, 4, 72, 52));
+    screen
+        .draw(Rect(
+            Paint::radial([
+                (0.0, Color::WHITE),
+                (0.5, Color::rgb(0xE0A020)),
+                (1.0, Color::argb(0x00E0A020)),
+            ])
+            .center(0.3, 0.3),
+            0,
+        ))
+        .component(at(164, 4, 72, 52));
+
+    screen
+        .draw(Div {
+            style: DivStyle::new()
+                .background(Paint::linear(
+                    180.0,
+                    [(0.0, Color::rgb(0x202020)), (1.0, Color::rgb(0x40A040))],
+                ))
+                .corner_radius(Corners::new(16, 0, 16, 0)),
+            ..Default::default()
+        })
+        .component(at(4, 64, 100, 52));
+    screen
+        .draw(
+            Text::new("Gradient")
+                .size(30.0)
+                .color(Paint::linear(90.0, stops)),
+        )
+        .component(at(112, 72, 0, 0));
+
+    check("gradients", &mut screen);
+
+    // Left to right from red to blue, with the middle halfway between.
+    assert_close(pixel(&screen, 4, 30), 0xE04040);
+    assert_close(pixel(&screen, 75, 30), 0x4060E0);
+    let middle = pixel(&screen, 40, 30);
+    assert!(
+        (0x8F..=0x92).contains(&(middle >> 16)),
+        "middle {:06X}",
+        middle
+    );
+    // Each column holds one color, dithered by at most one step between rows.
+    for x in [20, 40, 60] {
+        let column: Vec<u32> = (4..56).map(|y| pixel(&screen, x, y)).collect();
+        let red = |c: &u32| c >> 16;
+        let (lo, hi) = (column.iter().map(red).min(), column.iter().map(red).max());
+        assert!(
+            hi.unwrap() - lo.unwrap() <= 1,
+            "column {} spans {:?}..{:?}",
+            x,
+            lo,
+            hi
+        );
+    }
+
+    // The radial gradient is white at its center and fades out at the far corner.
+    assert_close(pixel(&screen, 164 + 21, 4 + 15), 0xFFFFFF);
+    let corner = pixel(&screen, 235, 55);
+    assert!(corner >> 16 < 8, "corner {:06X}", corner);
+}
+
+#[test]
+fn borders() {
+    let mut screen = Screen::headless(240, 120);
+
+    let borders = [
+        (Border::new(2, Color::WHITE), Corners::from(0), None),
+        (
+            Border {
+                width: Sides::new(2, 6, 10, 4),
+                color: Sides::new(
+                    Color::rgb(0xE04040),
+                    Color::rgb(0x40A040),
+                    Color::rgb(0x4060E0),
+                    Color::rgb(0xE0A020),
+                ),
+            },
+            Corners::from(14),
+            None,
+        ),
+        (
+            Border::new(3, Color::rgb(0x4060E0)),
+            Corners::new(0, 20, 4, 30),
+            Some(Outline::new(2, Color::rgb(0xE0A020)).offset(3)),
+        ),
+    ];
+    for (i, (border, radius, outline)) in borders.into_iter().enumerate() {
+        screen
+            .draw(Div {
+                style: DivStyle {
+                    outline,
+                    ..DivStyle::new()
+                        .background(0x303030)
+                        .border(border)
+                        .corner_radius(radius)
+                },
+                ..Default::default()
+            })
+            .component(at(12 + i as i32 * 76, 16, 64, 88));
+    }
+
+    check("borders", &mut screen);
+
+    // Each side of the second box keeps its own color.
+    let (red, green, blue, gold) = (0xE04040, 0x40A040, 0x4060E0, 0xE0A020);
+    assert_eq!(pixel(&screen, 120, 17), red);
+    assert_eq!(pixel(&screen, 149, 60), green);
+    assert_eq!(pixel(&screen, 120, 99), blue);
+    assert_eq!(pixel(&screen, 89, 60), gold);
+    assert_eq!(pixel(&screen, 120, 60), 0x303030);
+    // Where the 2 wide top meets the 4 wide left, pixels on the seam blend the two.
+    for x in [94, 95] {
+        let seam = pixel(&screen, x, 19);
+        let between = (0..3).all(|i| {
+            let channel = |c: u32| c >> (i * 8) & 0xFF;
+            let (lo, hi) = (
+                channel(red).min(channel(gold)),
+                channel(red).max(channel(gold)),
+            );
+            (lo..=hi).contains(&channel(seam))
+        });
+        assert!(between && seam != red && seam != gold, "seam {:06X}", seam);
+    }
+
+    // The third box has a square top left corner, a rounded top right one and
+    // an outline 3px out.
+    assert_eq!(pixel(&screen, 164, 16), blue);
+    assert_eq!(pixel(&screen, 227, 16), 0x000000);
+    assert_eq!(pixel(&screen, 164 - 4, 60), gold);
+    assert_eq!(pixel(&screen, 164 - 2, 60), 0x000000);
+}
+
+#[test]
+fn image_decoding() {
+    let image = ImageData::decode(&bmp()).unwrap();
+    assert_eq!((image.width, image.height), (2, 2));
+    assert_eq!(
+        image.pixels,
+        [0xFFFF0000, 0xFF00FF00, 0xFF0000FF, 0xFFFFFFFF]
+    );
+    assert!(ImageData::decode(b"GIF89a").is_err());
+    assert!(ImageData::decode(&bmp()[..60]).is_err());
+
+    // Loads of the same file or bytes share one decoded image.
+    let png = golden("image");
+    let first = image::load(&png).unwrap();
+    assert!(Arc::ptr_eq(&first, &image::load(&png).unwrap()));
+    assert_eq!((first.width, first.height), (240, 120));
+    let bytes = std::fs::read(&png).unwrap();
+    let decoded = image::from_bytes(&bytes).unwrap();
+    assert!(Arc::ptr_eq(&decoded, &image::from_bytes(&bytes).unwrap()));
+    assert_eq!(decoded.pixels, first.pixels);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::{at, check};
+use osgui::{
+    backend::HeadlessBackend,
+    color::Color,
+    elements::{
+        div::{Div, DivStyle},
+        Rect,
+    },
+    style::{BoxShadow, Transform},
+    Screen,
+};
+
+fn card(style: DivStyle) -> Div {
+    Div {
+        style: DivStyle {
+            background_color: Some(Color::rgb(0xF4F4F4).into()),
+            corner_radius: Some(12.into()),
+            ..style
+        },
+        ..Default::default()
+    }
+}
+
+/// Draws `cards` over a light gray background.
+fn scene(cards: Vec<(Div, Transform)>) -> Screen<HeadlessBackend> {
+    let mut screen = Screen::headless(240, 120);
+    screen
+        .draw(Rect(Color::rgb(0xC8C8C8).into(), 0))
+        .component(at(0, 0, 240, 120));
+    for (card, transform) in cards {
+        screen.draw(card).component(transform);
+    }
+    screen
+}
+
+#[test]
+fn outer_shadows() {
+    let mut screen = scene(vec![
+        (
+            card(DivStyle {
+                box_shadow: Some(vec![BoxShadow::new(4, 6, 12, Color::argb(0x80000000))]),
+                ..Default::default()
+            }),
+            at(20, 24, 80, 64),
+        ),
+        (
+            card(DivStyle {
+                box_shadow: Some(vec![
+                    BoxShadow::new(0, 0, 0, Color::rgb(0x3D7BFD)).spread(4),
+                    BoxShadow::new(0, 8, 16, Color::argb(0x60000000)).spread(-2),
+                ]),
+                ..Default::default()
+            }),
+            at(140, 24, 80, 64),
+        ),
+    ]);
+    check("outer_shadows", &mut screen);
+}
+
+#[test]
+fn inset_shadows() {
+    let mut screen = scene(vec![
+        (
+            card(DivStyle {
+                box_shadow: Some(vec![
+                    BoxShadow::new(4, 4, 10, Color::argb(0x90000000)).inset()
+                ]),
+                ..Default::default()
+            }),
+            at(20, 24, 80, 64),
+        ),
+        (
+            card(DivStyle {
+                box_shadow: Some(vec![BoxShadow::new(0, 0, 6, Color::rgb(0xE04040))
+                    .spread(6)
+                    .inset()]),
+                ..Default::default()
+            }),
+            at(140, 24, 80, 64),
+        ),
+    ]);
+    check("inset_shadows", &mut screen);
+}
+
+#[test]
+fn backdrop_blur() {
+    let mut screen = Screen::headless(240, 120);
+    for i in 0..12 {
+        let color = if i % 2 == 0 { 0xE04040 } else { 0x4040E0 };
+        screen
+            .draw(Rect(Color::rgb(color).into(), 0))
+            .component(at(i * 20, 0, 10, 120));
+    }
+    // Small blurs run at full size and wide ones on a downsampled copy.
+    for (x, blur) in [(20, 2), (130, 12)] {
+        screen
+            .draw(Div {
+                style: DivStyle {
+                    backdrop_blur: Some(blur),
+                    corner_radius: Some(12.into()),
+                    background_color: Some(Color::rgba(255, 255, 255, 0x30).into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .component(at(x, 20, 90, 80));
+    }
+    check("backdrop_blur", &mut screen);
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::at;
+use minifb::{Key, MouseButton};
+use osgui::{
+    backend::HeadlessBackend,
+    color::Color,
+    elements::{div::Div, Rect},
+    extensions::keyboard::{Focusable, Focused, KeyboardExtension},
+    widget::Widget,
+    Screen,
+};
+
+/// Three focusable boxes, the last one inside a div, after a first frame.
+fn screen() -> (Screen<HeadlessBackend>, Vec<Arc<Widget>>) {
+    let mut screen = Screen::headless(200, 100);
+    screen.extension(KeyboardExtension::new());
+
+    let square = || Rect(Color::WHITE.into(), 0);
+    let a = screen
+        .draw(square())
+        .component(at(0, 0, 40, 40))
+        .component(Focusable)
+        .clone();
+    let b = screen
+        .draw(square())
+        .component(at(50, 0, 40, 40))
+        .component(Focusable)
+        .clone();
+    let mut div = Div::default();
+    let c = div
+        .draw(square())
+        .component(at(10, 10, 40, 40))
+        .component(Focusable)
+        .clone();
+    screen.draw(div).component(at(100, 0, 60, 60));
+
+    screen.frame().unwrap();
+    (screen, vec![a, b, c])
+}
+
+fn press(screen: &mut Screen<HeadlessBackend>, key: Key) {
+    screen.backend_mut().press_key(key);
+    screen.frame().unwrap();
+    screen.backend_mut().release_key(key);
+    screen.frame().unwrap();
+}
+
+fn click(screen: &mut Screen<HeadlessBackend>, x: f32, y: f32) {
+    let backend = screen.backend_mut();
+    backend.set_mouse_pos(Some((x, y)));
+    backend.set_mouse_down(MouseButton::Left, true);
+    screen.frame().unwrap();
+    screen
+        .backend_mut()
+        .set_mouse_down(MouseButton::Left, false);
+    screen.frame().unwrap();
+}
+
+/// The index of the one focused widget, if any.
+fn focused(widgets: &[Arc<Widget>]) -> Option<usize> {
+    let focused: Vec<usize> = (0..widgets.len())
+        .filter(|&i| widgets[i].has::<Focused>())
+        .collect();
+    assert!(focused.len() <= 1, "several widgets focused: {:?}", focused);
+    focused.first().copied()
+}
+
+#[test]
+fn tab_order() {
+    let (mut screen, widgets) = screen();
+    assert_eq!(focused(&widgets), None);
+
+    let mut order = Vec::new();
+    for _ in 0..4 {
+        press(&mut screen, Key::Tab);
+        order.push(focused(&widgets));
+    }
+    // Nested widgets follow in tree order, then focus wraps around.
+    assert_eq!(order, [Some(0), Some(1), Some(2), Some(0)]);
+}
+
+#[test]
+fn shift_tab_goes_back() {
+    let (mut screen, widgets) = screen();
+
+    screen.backend_mut().press_key(Key::LeftShift);
+    press(&mut screen, Key::Tab);
+    assert_eq!(focused(&widgets), Some(2));
+    press(&mut screen, Key::Tab);
+    assert_eq!(focused(&widgets), Some(1));
+    screen.backend_mut().release_key(Key::LeftShift);
+
+    press(&mut screen, Key::Tab);
+    assert_eq!(focused(&widgets), Some(2));
+}
+
+#[test]
+fn click_to_focus() {
+    let (mut screen, widgets) = screen();
+
+    click(&mut screen, 120.0, 20.0);
+    assert_eq!(focused(&widgets), Some(2));
+    click(&mut screen, 60.0, 10.0);
+    assert_eq!(focused(&widgets), Some(1));
+
+    // The div around the last box isn't focusable, nor is the background.
+    click(&mut screen, 105.0, 5.0);
+    assert_eq!(focused(&widgets), None);
+    click(&mut screen, 20.0, 20.0);
+    click(&mut screen, 190.0, 90.0);
+    assert_eq!(focused(&widgets), None);
+}
+
+#[test]
+fn focused_component() {
+    let (mut screen, widgets) = screen();
+
+    widgets[1].set_component(Focused);
+    screen.frame().unwrap();
+    assert_eq!(focused(&widgets), Some(1));
+
+    // Tab carries on from there.
+    press(&mut screen, Key::Tab);
+    assert_eq!(focused(&widgets), Some(2));
+
+    widgets[2].remove_component::<Focused>();
+    screen.frame().unwrap();
+    assert_eq!(focused(&widgets), None);
+    press(&mut screen, Key::Tab);
+    assert_eq!(focused(&widgets), Some(0));
+}
@@ -0,0 +1,95 @@
+//! The font registry is global, so every test here registers its own families.
+
+use std::sync::Arc;
+
+use osgui::{
+    render::font,
+    style::{FontWeight, TextStyle},
+};
+
+fn dejavu() -> Vec<u8> {
+    std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/DejaVuSans.ttf"
+    ))
+    .unwrap()
+}
+
+fn register(family: &str, weight: FontWeight, italic: bool) {
+    font::register_font(family, weight, italic, dejavu()).unwrap();
+}
+
+fn style(family: &str, weight: FontWeight, italic: bool) -> TextStyle {
+    TextStyle {
+        family: Some(family.to_string()),
+        weight,
+        italic,
+        ..Default::default()
+    }
+}
+
+/// The face ids of the chain for `family` at the normal weight.
+fn chain(family: &str) -> Vec<usize> {
+    let chain = font::font_chain(&style(family, FontWeight::NORMAL, false));
+    chain.iter().map(|f| f.id).collect()
+}
+
+#[test]
+fn family_matching() {
+    register("Match Sans", FontWeight::NORMAL, false);
+    register("Match Sans", FontWeight::BOLD, false);
+    register("Match Sans", FontWeight::NORMAL, true);
+    let face = |weight, italic| font::font_chain(&style("Match Sans", weight, italic))[0].clone();
+
+    let (regular, bold, italic) = (
+        face(FontWeight::NORMAL, false).id,
+        face(FontWeight::BOLD, false).id,
+        face(FontWeight::NORMAL, true).id,
+    );
+    assert!(regular != bold && bold != italic && italic != regular);
+
+    // The closest weight wins, and names match regardless of case.
+    assert_eq!(face(FontWeight::SEMI_BOLD, false).id, bold);
+    assert_eq!(face(FontWeight::LIGHT, false).id, regular);
+    let lowercase = font::font_chain(&style("match sans", FontWeight::BOLD, false));
+    assert_eq!(lowercase[0].id, bold);
+
+    // Italic matters more than weight; what's missing is synthesized.
+    let bold_italic = face(FontWeight::BOLD, true);
+    assert_eq!(bold_italic.id, italic);
+    assert!(bold_italic.fake_bold && !bold_italic.fake_italic);
+    let semi = face(FontWeight::SEMI_BOLD, false);
+    assert!(!semi.fake_bold && !semi.fake_italic);
+
+    // Unknown families use the default one, which is the bundled font here.
+    assert_eq!(chain("No Such Family")[0], 0);
+}
+
+#[test]
+fn fallback_chains() {
+    for family in ["Brand", "Brand Icons", "Other", "Shared"] {
+        register(family, FontWeight::NORMAL, false);
+    }
+    let id = |family| chain(family)[0];
+    let (brand, icons, other, shared) = (id("Brand"), id("Brand Icons"), id("Other"), id("Shared"));
+
+    // Each family gets its own fallbacks, then the bundled font.
+    font::set_family_fallback("Brand", &["Brand Icons", "Missing"]);
+    assert_eq!(chain("Brand"), [brand, icons, 0]);
+    assert_eq!(chain("Other"), [other, 0]);
+
+    // Global fallbacks come after a family's own, without repeating a face.
+    font::set_fallback(&["Shared", "Brand Icons"]);
+    assert_eq!(chain("Brand"), [brand, icons, shared, 0]);
+    assert_eq!(chain("Other"), [other, shared, icons, 0]);
+    font::set_fallback(&[]);
+
+    // Chains are resolved once per style until the registry changes.
+    let normal = style("Brand", FontWeight::NORMAL, false);
+    let first = font::font_chain(&normal);
+    assert!(Arc::ptr_eq(&first, &font::font_chain(&normal)));
+    font::set_family_fallback("Brand", &[]);
+    let changed = font::font_chain(&normal);
+    assert!(!Arc::ptr_eq(&first, &changed));
+    assert_eq!(changed.iter().map(|f| f.id).collect::<Vec<_>>(), [brand, 0]);
+}
@@ -0,0 +1,396 @@
+mod common;
+
+use common::{at, bounds, check, pixel};
+use osgui::{
+    color::Color,
+    elements::{
+        div::{Div, DivStyle, Overflow},
+        flex::{Align, Flex, FlexItem, Justify},
+        grid::{Grid, GridItem, Track},
+        scroll::{Scroll, ScrollView},
+        Rect,
+    },
+    extensions::scroll::ScrollExtension,
+    style::{Border, Dimension, Position, Sides, Transform},
+    Screen,
+};
+
+const COLORS: [u32; 4] = [0xE04040, 0x40A040, 0x4060E0, 0xE0A020];
+
+fn block(i: usize) -> Rect {
+    Rect(Color::rgb(COLORS[i % COLORS.len()]).into(), 4)
+}
+
+fn sized(width: usize, height: usize) -> Transform {
+    Transform::new().dimensions(width, height)
+}
+
+/// A fixed position with `Auto` dimensions.
+fn at_pos(x: i32, y: i32) -> Transform {
+    Transform::new().pos(Position::Const(x), Position::Const(y))
+}
+
+fn panel() -> DivStyle {
+    DivStyle::new().background(0x202020)
+}
+
+#[test]
+fn flex() {
+    let mut screen = Screen::headless(240, 160);
+
+    let mut row = Flex {
+        style: panel(),
+        ..Flex::row()
+    }
+    .gap(8)
+    .padding(8)
+    .align(Align::Center);
+    let fixed = row.draw(block(0)).component(sized(30, 20)).clone();
+    let grow_one = row
+        .draw(block(1))
+        .component(sized(30, 40))
+        .component(FlexItem::grow(1.0))
+        .clone();
+    let grow_two = row
+        .draw(block(2))
+        .component(sized(30, 20))
+        .component(FlexItem::grow(2.0).align(Align::End))
+        .clone();
+    screen.draw(row).component(at(0, 0, 240, 56));
+
+    let mut column = Flex {
+        style: panel(),
+        ..Flex::column()
+    }
+    .gap(4)
+    .padding(8)
+    .justify(Justify::SpaceBetween)
+    .align(Align::Stretch);
+    let stretched: Vec<_> = (0..3)
+        .map(|i| {
+            column
+                .draw(block(i + 1))
+                .component(Transform::new().height(Dimension::Const(16)))
+                .clone()
+        })
+        .collect();
+    screen.draw(column).component(at(0, 64, 112, 96));
+
+    let mut centered = Flex {
+        style: panel(),
+        ..Flex::row()
+    }
+    .gap(6)
+    .justify(Justify::SpaceEvenly)
+    .align(Align::End);
+    for i in 0..3 {
+        centered.draw(block(i)).component(sized(20, 20 + i * 10));
+    }
+    screen.draw(centered).component(at(120, 64, 120, 96));
+
+    check("flex", &mut screen);
+
+    // The 118px left over is shared 1:2 between the growing items.
+    assert_eq!(bounds(&fixed), (8, 18, 30, 20));
+    assert_eq!(bounds(&grow_one), (46, 8, 69, 40));
+    assert_eq!(bounds(&grow_two), (123, 28, 108, 20));
+    // Stretched across the column, spread from its top to its bottom.
+    let column: Vec<_> = stretched.iter().map(bounds).collect();
+    assert_eq!(
+        column,
+        [(8, 72, 96, 16), (8, 104, 96, 16), (8, 136, 96, 16)]
+    );
+}
+
+#[test]
+fn grid() {
+    let mut screen = Screen::headless(240, 160);
+
+    let mut grid = Grid {
+        style: panel(),
+        ..Grid::new(
+            vec![Track::Fixed(40), Track::Auto, Track::Fraction(1.0)],
+            vec![Track::Auto, Track::Fraction(1.0), Track::Fixed(24)],
+        )
+    }
+    .gap(6, 6)
+    .padding(8);
+    let fill = || {
+        Transform::new()
+            .width(Dimension::Fill)
+            .height(Dimension::Fill)
+    };
+    // The first row and the second column take the size of their content.
+    let first = grid.draw(block(0)).component(sized(40, 30)).clone();
+    let auto = grid.draw(block(1)).component(sized(60, 20)).clone();
+    let fraction = grid
+        .draw(block(2))
+        .component(
+            Transform::new()
+                .width(Dimension::Fill)
+                .height(Dimension::Const(20)),
+        )
+        .clone();
+    let tall = grid
+        .draw(block(3))
+        .component(fill())
+        .component(GridItem::at(1, 0).span(2, 1))
+        .clone();
+    let wide = grid
+        .draw(block(0))
+        .component(
+            Transform::new()
+                .width(Dimension::Const(90))
+                .height(Dimension::Fill),
+        )
+        .component(GridItem::at(1, 1).span(1, 2))
+        .clone();
+    let last = grid.draw(block(1)).component(sized(30, 24)).clone();
+    screen.draw(grid).component(at(0, 0, 240, 160));
+
+    check("grid", &mut screen);
+
+    // Columns of 40, 84 and 88: the auto column grows to fit the 90 wide item
+    // spanning it and the fraction, which takes the rest of the 224 inside.
+    // Rows of 30, 78 and 24: the auto row fits the tallest item in it.
+    assert_eq!(bounds(&first), (8, 8, 40, 30));
+    assert_eq!(bounds(&auto), (54, 8, 60, 20));
+    assert_eq!(bounds(&fraction), (144, 8, 88, 20));
+    assert_eq!(bounds(&tall), (8, 44, 40, 78 + 6 + 24));
+    assert_eq!(bounds(&wide), (54, 44, 90, 78));
+    assert_eq!(bounds(&last), (54, 128, 30, 24));
+}
+
+#[test]
+fn scroll() {
+    let mut screen = Screen::headless(240, 160);
+
+    let mut views = Vec::new();
+    for (x, offset) in [(0, 0.0), (124, 90.0)] {
+        let mut view = ScrollView {
+            style: panel(),
+            ..ScrollView::new()
+        }
+        .inertia(false);
+        let mut list = Flex::column().gap(4).padding(4);
+        let first = list.draw(block(0)).component(sized(80, 30)).clone();
+        for i in 1..8 {
+            list.draw(block(i)).component(sized(80, 30));
+        }
+        // The list sizes itself to its 8 items.
+        view.draw(list);
+        view.scroll.scroll_to(0.0, offset);
+        let scroll = view.scroll.clone();
+        screen.draw(view).component(at(x, 0, 116, 160));
+        views.push((scroll, first));
+    }
+
+    check("scroll", &mut screen);
+
+    let content = 8 * 34 + 4;
+    for (scroll, first) in &views {
+        let offset = scroll.offset().1;
+        assert_eq!(bounds(first).1, 4 - offset as i32);
+        // The thumb is as much shorter than the track as the viewport is
+        // than the content, and moves with the offset.
+        let (pos, len) = scroll.thumb().expect("the list overflows the view");
+        assert_eq!(len, 160 * 160 / content);
+        assert_eq!(
+            pos,
+            ((160 - len) as f32 * offset / (content - 160) as f32).round() as i32
+        );
+    }
+    assert_eq!(views[1].0.offset(), (0.0, 90.0));
+}
+
+#[test]
+fn padding() {
+    let mut screen = Screen::headless(240, 120);
+
+    let fill = || {
+        Transform::new()
+            .width(Dimension::Fill)
+            .height(Dimension::Fill)
+    };
+    let boxes: [(Sides<usize>, Option<Border>); 3] = [
+        (Sides::all(8), None),
+        (Sides::new(4, 16, 24, 8), None),
+        (Sides::symmetric(12, 6), Some(Border::new(4, Color::WHITE))),
+    ];
+    let mut children = Vec::new();
+    for (i, (padding, border)) in boxes.into_iter().enumerate() {
+        let mut div = Div {
+            style: DivStyle {
+                border,
+                ..DivStyle::new().padding(padding).background(0x404040)
+            },
+            ..Default::default()
+        };
+        children.push(div.draw(block(i)).component(fill()).clone());
+        screen
+            .draw(div)
+            .component(at(i as i32 * 80 + 4, 4, 72, 112));
+    }
+
+    check("padding", &mut screen);
+
+    // Children fill the 72 x 112 boxes inside the border and padding.
+    assert_eq!(bounds(&children[0]), (4 + 8, 4 + 8, 72 - 16, 112 - 16));
+    assert_eq!(bounds(&children[1]), (84 + 8, 4 + 4, 72 - 24, 112 - 28));
+    assert_eq!(
+        bounds(&children[2]),
+        (164 + 4 + 6, 4 + 4 + 12, 72 - 20, 112 - 32)
+    );
+}
+
+#[test]
+fn containers_fit_children() {
+    let mut screen = Screen::headless(240, 160);
+
+    let mut row = Flex {
+        style: panel(),
+        ..Flex::row()
+    }
+    .gap(4)
+    .padding(8);
+    row.draw(block(0)).component(sized(30, 20));
+    row.draw(block(1)).component(sized(40, 10).margins(2));
+    let row = screen.draw(row).component(Transform::new()).clone();
+
+    let mut column = Flex::column().gap(6).padding(4);
+    for i in 0..3 {
+        column.draw(block(i)).component(sized(20 + i * 10, 10));
+    }
+    let column = screen.draw(column).component(at_pos(150, 0)).clone();
+
+    let mut div = Div {
+        style: panel().padding(6),
+        ..Default::default()
+    };
+    div.draw(block(2)).component(at(10, 5, 20, 20));
+    let div = screen.draw(div).component(at_pos(0, 100)).clone();
+
+    let mut grid = Grid::new(vec![Track::Fixed(30), Track::Auto], vec![]).gap(4, 4);
+    grid.draw(block(0)).component(sized(30, 10));
+    grid.draw(block(1)).component(sized(25, 15));
+    grid.draw(block(2)).component(sized(10, 10));
+    let grid = screen.draw(grid).component(at_pos(100, 100)).clone();
+
+    screen.frame().unwrap();
+    assert_eq!(bounds(&div), (0, 100, 6 + 10 + 20 + 6, 6 + 5 + 20 + 6));
+    assert_eq!(bounds(&grid), (100, 100, 30 + 4 + 25, 15 + 4 + 10));
+    assert_eq!(bounds(&row), (0, 0, 8 + 30 + 4 + 44 + 8, 8 + 20 + 8));
+    assert_eq!(
+        bounds(&column),
+        (150, 0, 4 + 40 + 4, 4 + 3 * 10 + 2 * 6 + 4)
+    );
+}
+
+#[test]
+fn div_overflow_scroll() {
+    let mut screen = Screen::headless(240, 160);
+    screen.extension(ScrollExtension::new());
+
+    let mut div = Div {
+        style: panel().padding(4).overflow(Overflow::Scroll),
+        ..Default::default()
+    };
+    let mut list = Flex::column().gap(4);
+    let items: Vec<_> = (0..8)
+        .map(|i| list.draw(block(i)).component(sized(80, 30)).clone())
+        .collect();
+    div.draw(list);
+    let div = screen.draw(div).component(at(20, 0, 116, 100)).clone();
+    screen.frame().unwrap();
+    assert_eq!(bounds(&items[0]), (24, 4, 80, 30));
+
+    // One wheel step down scrolls the 40px the extension moves per unit.
+    let backend = screen.backend_mut();
+    backend.set_mouse_pos(Some((60.0, 50.0)));
+    backend.set_scroll_wheel(Some((0.0, -1.0)));
+    screen.frame().unwrap();
+    screen.frame().unwrap();
+    assert_eq!(bounds(&items[0]).1, 4 - 40);
+    assert_eq!(div.get::<Scroll>().unwrap().offset(), (0.0, 40.0));
+
+    // The content is 8 * 34 - 4 tall plus the padding, so it stops there.
+    div.get::<Scroll>().unwrap().scroll_to(0.0, 1000.0);
+    screen.frame().unwrap();
+    let bottom = 8 * 34 - 4 + 8 - 100;
+    assert_eq!(div.get::<Scroll>().unwrap().offset(), (0.0, bottom as f32));
+    assert_eq!(bounds(&items[7]).1, 4 + 7 * 34 - bottom);
+}
+
+#[test]
+fn relative_dimensions() {
+    let mut screen = Screen::headless(200, 100);
+    let mut draw = |width: Dimension, height: Dimension, x: i32, y: i32| {
+        screen
+            .draw(block(0))
+            .component(at_pos(x, y).width(width).height(height))
+            .clone()
+    };
+
+    let half = draw(Dimension::Percent(50.0), Dimension::Percent(25.0), 0, 0);
+    let fill = draw(Dimension::Fill, Dimension::Fill, 20, 30);
+    let clamped_up = draw(Dimension::Percent(10.0).min(40), Dimension::Const(10), 0, 0);
+    let clamped_down = draw(
+        Dimension::Percent(80.0).max(150),
+        Dimension::Const(10),
+        0,
+        0,
+    );
+    let margined = screen
+        .draw(block(1))
+        .component(at_pos(20, 0).width(Dimension::Fill).margins(5))
+        .clone();
+
+    screen.frame().unwrap();
+    assert_eq!(bounds(&half), (0, 0, 100, 25));
+    assert_eq!(bounds(&fill), (20, 30, 180, 70));
+    assert_eq!(bounds(&clamped_up).2, 40);
+    assert_eq!(bounds(&clamped_down).2, 150);
+    // `Fill` stops short of the margins on both sides.
+    assert_eq!(bounds(&margined).2, 200 - 20 - 10);
+
+    // Relative sizes follow the window.
+    screen.backend_mut().resize(400, 100);
+    screen.frame().unwrap();
+    assert_eq!(bounds(&half).2, 200);
+    assert_eq!(bounds(&fill).2, 380);
+    assert_eq!(bounds(&clamped_up).2, 40);
+    assert_eq!(bounds(&clamped_down).2, 150);
+}
+
+#[test]
+fn off_screen_positions() {
+    let mut screen = Screen::headless(40, 40);
+    let white = || Rect(Color::WHITE.into(), 0);
+
+    // Partly above and left of the buffer: only the visible corner is drawn.
+    screen.draw(white()).component(at(-10, -10, 20, 20));
+    // Pushed entirely off-screen by its offset.
+    let gone = screen
+        .draw(white())
+        .component(at(0, 30, 10, 10).offset(-100, 0))
+        .clone();
+    // A child larger than its parent hangs over the parent's start.
+    let mut parent = Div::default();
+    let child = parent
+        .draw(Rect(Color::rgb(COLORS[2]).into(), 0))
+        .component(sized(16, 16).pos(Position::End, Position::End))
+        .clone();
+    screen.draw(parent).component(at(30, 30, 8, 8));
+
+    screen.frame().unwrap();
+    assert_eq!(pixel(&screen, 0, 0), 0xFFFFFF);
+    assert_eq!(pixel(&screen, 9, 9), 0xFFFFFF);
+    assert_eq!(pixel(&screen, 10, 10), 0x000000);
+
+    assert_eq!(bounds(&gone), (-100, 30, 10, 10));
+    assert_eq!(pixel(&screen, 0, 35), 0x000000);
+
+    assert_eq!(bounds(&child), (22, 22, 16, 16));
+    assert_eq!(pixel(&screen, 22, 22), COLORS[2]);
+    assert_eq!(pixel(&screen, 21, 21), 0x000000);
+}
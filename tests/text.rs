@@ -0,0 +1,405 @@
+mod common;
+
+use std::sync::Mutex;
+
+use common::{at, bounds, check, element, send};
+use minifb::Key;
+use osgui::{
+    backend::{Backend, HeadlessBackend},
+    color::Color,
+    elements::{
+        div::DivStyle,
+        input::{OnChange, TextInput, TextInputStyle},
+        richtext::{OnLinkClick, RichText, Span},
+        text::{Text, TextAlign, TextWrap},
+        textarea::TextArea,
+    },
+    events::{CharInput, Click, Drag, FocusIn, KeyDown, Modifiers},
+    style::{Border, Dimension, FontWeight, Position, Transform},
+    Screen,
+};
+
+fn field() -> DivStyle {
+    DivStyle::new()
+        .background(0x202020)
+        .corner_radius(6)
+        .border(Border::new(1, Color::rgb(0x606060)))
+}
+
+fn text_size(size: f32) -> TextInputStyle {
+    TextInputStyle {
+        size,
+        ..Default::default()
+    }
+}
+
+/// A fixed position with `Auto` dimensions.
+fn at_pos(x: i32, y: i32) -> Transform {
+    Transform::new().pos(Position::Const(x), Position::Const(y))
+}
+
+/// The first and last columns with any ink in rows `y0..y1` of the last frame.
+fn ink(screen: &Screen<HeadlessBackend>, y0: usize, y1: usize) -> (usize, usize) {
+    let (width, _) = screen.backend().get_size();
+    let columns: Vec<usize> = (0..width)
+        .filter(|&x| (y0..y1).any(|y| screen.get_buffer()[y * width + x] != 0))
+        .collect();
+    (columns[0], *columns.last().unwrap())
+}
+
+#[test]
+fn text() {
+    let mut screen = Screen::headless(240, 200);
+
+    let line = screen
+        .draw(Text::new("One line").size(16.0))
+        .component(at_pos(8, 4))
+        .clone();
+    let wrapped = screen
+        .draw(
+            Text::new("Wrapping text breaks between words")
+                .size(16.0)
+                .max_width(120),
+        )
+        .component(at_pos(8, 28))
+        .clone();
+    let cut = screen
+        .draw(
+            Text::new("A line far too long to fit")
+                .size(16.0)
+                .wrap(TextWrap::None)
+                .max_width(100)
+                .ellipsis(true),
+        )
+        .component(at_pos(8, 104))
+        .clone();
+    for (i, align) in [TextAlign::Left, TextAlign::Center, TextAlign::Right]
+        .into_iter()
+        .enumerate()
+    {
+        screen
+            .draw(Text::new("Aligned").size(16.0).align(align))
+            .component(at_pos(8, 128 + i as i32 * 24).width(Dimension::Const(224)));
+    }
+
+    check("text", &mut screen);
+
+    // Blocks are as wide as their widest line, which `max_width` caps.
+    let (_, _, line_w, line_h) = bounds(&line);
+    let (_, _, wrapped_w, wrapped_h) = bounds(&wrapped);
+    assert!(line_w < 80, "one line is {} wide", line_w);
+    assert!(
+        wrapped_w <= 120 && wrapped_w > 80,
+        "wrapped is {} wide",
+        wrapped_w
+    );
+    assert_eq!(wrapped_h.div_ceil(line_h), 3);
+    let (_, _, cut_w, cut_h) = bounds(&cut);
+    assert!(cut_w <= 100 && cut_w > 80, "cut line is {} wide", cut_w);
+    assert_eq!(cut_h, line_h);
+    assert!(ink(&screen, 104, 104 + line_h).1 < 8 + 100);
+
+    let spans: Vec<(usize, usize)> = (0..3)
+        .map(|i| ink(&screen, 128 + i * 24, 128 + i * 24 + line_h))
+        .collect();
+    let margins: Vec<(usize, usize)> = spans.iter().map(|&(l, r)| (l - 8, 8 + 224 - r)).collect();
+    assert!(margins[0].0 <= 2, "left aligned at {:?}", margins[0]);
+    assert!(
+        margins[1].0.abs_diff(margins[1].1) <= 2,
+        "centered at {:?}",
+        margins[1]
+    );
+    assert!(margins[2].1 <= 3, "right aligned at {:?}", margins[2]);
+}
+
+/// The columns in row `y` of the last frame with more than faint ink.
+fn inked(screen: &Screen<HeadlessBackend>, y: usize) -> Vec<usize> {
+    let (width, _) = screen.backend().get_size();
+    (0..width)
+        .filter(|&x| screen.get_buffer()[y * width + x] & 0xFF > 0x40)
+        .collect()
+}
+
+#[test]
+fn text_styles() {
+    let mut screen = Screen::headless(160, 240);
+    let word = || Text::new("HHH").size(24.0).color(Color::WHITE);
+    let styled = [
+        word(),
+        word().bold(),
+        word().italic(true),
+        word().underline(true),
+        word().strikethrough(true),
+        word().letter_spacing(4.0),
+    ]
+    .map(|text| screen.draw(text).component(at_pos(8, 0)).clone());
+    for (i, widget) in styled.iter().enumerate() {
+        widget.set_component(at_pos(8, i as i32 * 40));
+    }
+    screen.frame().unwrap();
+
+    // The inked columns of every row of line `i`.
+    let rows = |i: usize| -> Vec<Vec<usize>> {
+        let (_, y, _, h) = bounds(&styled[i]);
+        (y as usize..y as usize + h)
+            .map(|y| inked(&screen, y))
+            .collect()
+    };
+    let ink = |i: usize| rows(i).iter().map(Vec::len).sum::<usize>();
+    // An unbroken run across the word, as drawn by the decorations.
+    let solid_rows = |i: usize| -> Vec<usize> {
+        let rows = rows(i);
+        (0..rows.len())
+            .filter(|&y| {
+                let r = &rows[y];
+                r.len() > 30 && r.len() == r[r.len() - 1] - r[0] + 1
+            })
+            .collect()
+    };
+
+    let normal = ink(0);
+    assert!(ink(1) > normal * 5 / 4, "bold {} vs {}", ink(1), normal);
+
+    // Italic leans the stems right, so the top starts further in than the baseline.
+    let lean = |i: usize| {
+        let inked: Vec<Vec<usize>> = rows(i).into_iter().filter(|r| !r.is_empty()).collect();
+        inked[0][0] as i32 - inked[inked.len() - 1][0] as i32
+    };
+    assert!(lean(0).abs() <= 1, "upright leans {}", lean(0));
+    assert!(lean(2) >= 3, "italic leans {}", lean(2));
+
+    // The plain word has gaps between its letters on every row.
+    assert!(solid_rows(0).is_empty());
+    let (underline, strikethrough) = (solid_rows(3), solid_rows(4));
+    assert!(!underline.is_empty() && ink(3) > normal);
+    assert!(!strikethrough.is_empty() && ink(4) > normal);
+    assert!(
+        strikethrough.last() < underline.first(),
+        "strikethrough {:?} runs above the underline {:?}",
+        strikethrough,
+        underline
+    );
+
+    assert!(bounds(&styled[5]).2 >= bounds(&styled[0]).2 + 8);
+}
+
+#[test]
+fn input() {
+    let mut screen = Screen::headless(240, 120);
+
+    let inputs = [
+        TextInput::new("Search"),
+        TextInput::new("Search").value("Hello, world"),
+        TextInput::new("Name").value("A value too long to fit in the field"),
+    ];
+    let mut widgets = Vec::new();
+    for (i, input) in inputs.into_iter().enumerate() {
+        let mut input = input.style(text_size(18.0));
+        input.background = field();
+        let widget = screen
+            .draw(input)
+            .component(at(8, 8 + i as i32 * 40, 224, 0));
+        widgets.push(widget.clone());
+    }
+    // Focused inputs draw a caret and keep it in view.
+    send(&widgets[2], &FocusIn);
+
+    check("input", &mut screen);
+}
+
+#[test]
+fn input_editing() {
+    static CHANGED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    let mut screen = Screen::headless(240, 40);
+    let input = screen
+        .draw(TextInput::new("").value("Hello, world"))
+        .component(at(8, 8, 224, 0))
+        .component(OnChange(|_, value| {
+            CHANGED.lock().unwrap().push(value.to_string())
+        }))
+        .clone();
+    screen.frame().unwrap();
+    send(&input, &FocusIn);
+
+    let press = |key: Key, modifiers: Modifiers| {
+        send(
+            &input,
+            &KeyDown {
+                key,
+                repeat: false,
+                modifiers,
+            },
+        )
+    };
+    let state = || element(&input, |i: &mut TextInput| (i.caret(), i.selection()));
+    let (none, shift) = (
+        Modifiers::default(),
+        Modifiers {
+            shift: true,
+            ..Default::default()
+        },
+    );
+    let ctrl = Modifiers {
+        ctrl: true,
+        ..Default::default()
+    };
+
+    // Offsets are in bytes, and words run up to the next whitespace.
+    assert_eq!(state(), (12, None));
+    press(Key::Home, none);
+    press(Key::Right, ctrl);
+    assert_eq!(state(), (6, None));
+    press(Key::End, shift);
+    assert_eq!(state(), (12, Some((6, 12))));
+
+    // Typing replaces the selection; a two-byte char moves the caret by two.
+    send(&input, &CharInput('é'));
+    assert_eq!(state(), (8, None));
+    press(Key::Left, none);
+    assert_eq!(state(), (6, None));
+    press(Key::Backspace, ctrl);
+    assert_eq!(state(), (0, None));
+
+    // Copy and paste go through the app's own clipboard.
+    press(Key::A, ctrl);
+    press(Key::C, ctrl);
+    press(Key::End, none);
+    press(Key::V, ctrl);
+    assert_eq!(element(&input, |i: &mut TextInput| i.value.clone()), "éé");
+    assert_eq!(*CHANGED.lock().unwrap(), ["Hello,é", "é", "éé"]);
+}
+
+#[test]
+fn textarea() {
+    let mut screen = Screen::headless(240, 160);
+
+    let mut empty = TextArea::new("Write something");
+    empty.background = field();
+    screen.draw(empty).component(at(8, 8, 224, 40));
+
+    let mut area = TextArea::new("")
+        .value("Lines wrap at words when they get wider than the field.\nSecond line\n\nAfter a blank one")
+        .style(text_size(16.0));
+    area.background = field();
+    let area = screen.draw(area).component(at(8, 56, 224, 96)).clone();
+
+    screen.frame().unwrap();
+    send(&area, &FocusIn);
+    send(&area, &Click { x: 40.0, y: 12.0 });
+    send(&area, &Drag { x: 60.0, y: 52.0 });
+    // From inside the first word to the end of the wrapped first line.
+    let (selection, selected) =
+        element(&area, |a: &mut TextArea| (a.selection(), a.selected_text()));
+    assert_eq!(selection, Some(((0, 4), (0, 55))));
+    assert_eq!(
+        selected,
+        "s wrap at words when they get wider than the field."
+    );
+
+    check("textarea", &mut screen);
+}
+
+#[test]
+fn richtext() {
+    static CLICKED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    let mut screen = Screen::headless(240, 160);
+
+    let text = RichText::new(vec![
+        Span::new("Rich text mixes ").size(16.0).color(Color::WHITE),
+        Span::new("bold")
+            .size(16.0)
+            .bold()
+            .color(Color::rgb(0xE0A020)),
+        Span::new(", ").size(16.0).color(Color::WHITE),
+        Span::new("large").size(28.0).color(Color::rgb(0x4080F0)),
+        Span::new(" and ").size(16.0).color(Color::WHITE),
+        Span::new("linked")
+            .size(16.0)
+            .underline(true)
+            .color(Color::rgb(0x40C0F0))
+            .link("https://example.com"),
+        Span::new(" spans that wrap as one paragraph.")
+            .size(16.0)
+            .weight(FontWeight::LIGHT)
+            .color(Color::WHITE),
+    ])
+    .max_width(224);
+    let text = screen
+        .draw(text)
+        .component(at(8, 8, 224, 0))
+        .component(OnLinkClick(|_, link| {
+            CLICKED.lock().unwrap().push(link.to_string())
+        }))
+        .clone();
+
+    let centered = RichText::new(vec![
+        Span::new("Centered ").size(14.0).color(Color::WHITE),
+        Span::new("lines")
+            .size(14.0)
+            .italic(true)
+            .color(Color::WHITE),
+    ])
+    .align(TextAlign::Center)
+    .line_height(1.5)
+    .max_width(224);
+    screen.draw(centered).component(at(8, 120, 224, 0));
+
+    check("richtext", &mut screen);
+
+    // Every span is drawn somewhere, and only the link span has a link.
+    let points: Vec<(f32, f32)> = (0..112)
+        .flat_map(|y| (0..224).map(move |x| (x as f32, y as f32)))
+        .collect();
+    let (spans, links) = element(&text, |t: &mut RichText| {
+        let mut spans: Vec<usize> = points
+            .iter()
+            .filter_map(|&(x, y)| t.span_at(x, y))
+            .collect();
+        spans.sort();
+        spans.dedup();
+        let links: Vec<(f32, f32, String)> = points
+            .iter()
+            .filter_map(|&(x, y)| Some((x, y, t.link_at(x, y)?.to_string())))
+            .collect();
+        (spans, links)
+    });
+    assert_eq!(spans, (0..7).collect::<Vec<_>>());
+    assert!(links
+        .iter()
+        .all(|(_, _, link)| link == "https://example.com"));
+    let &(x, y, _) = links.first().expect("the link is drawn");
+    assert_eq!(element(&text, |t: &mut RichText| t.span_at(x, y)), Some(5));
+    assert_eq!(
+        element(&text, |t: &mut RichText| t.span_at(0.0, 150.0)),
+        None
+    );
+
+    // Clicks only report the link when they land on it.
+    send(&text, &Click { x: 0.0, y: 0.0 });
+    send(&text, &Click { x, y });
+    assert_eq!(*CLICKED.lock().unwrap(), ["https://example.com"]);
+}
+
+#[test]
+fn textarea_far_down() {
+    let mut screen = Screen::headless(240, 120);
+    let text: Vec<String> = (0..500)
+        .map(|i| format!("Line {} of a long text", i))
+        .collect();
+    let area = TextArea::new("")
+        .value(&text.join("\n"))
+        .style(text_size(16.0));
+    let area = screen.draw(area).component(at(0, 0, 240, 120)).clone();
+    screen.frame().unwrap();
+
+    // Jump to the end and let the caret scroll into view.
+    send(&area, &FocusIn);
+    element(&area, |a: &mut TextArea| a.set_caret((499, 0)));
+    screen.frame().unwrap();
+
+    send(&area, &Click { x: 4.0, y: 4.0 });
+    let (line, _) = element(&area, |a: &mut TextArea| a.caret());
+    assert!(line > 490 && line < 499, "clicked line {}", line);
+}
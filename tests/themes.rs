@@ -0,0 +1,146 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{at, bounds, check, pixel};
+use osgui::{
+    backend::HeadlessBackend,
+    color::Color,
+    elements::{
+        div::{Div, DivStyle},
+        text::Text,
+    },
+    style::{Border, BoxShadow, Transform},
+    theme::{Class, Style, Theme},
+    widget::Widget,
+    Screen,
+};
+
+fn dark() -> Theme {
+    Theme::new()
+        .root(
+            Style::new()
+                .background(Color::rgb(0x101010))
+                .color(Color::WHITE)
+                .size(16.0),
+        )
+        .class(
+            "card",
+            Style::new()
+                .background(Color::rgb(0x2A2A2A))
+                .corner_radius(12)
+                .padding(10)
+                .box_shadow(BoxShadow::new(0, 4, 12, Color::argb(0x80000000))),
+        )
+        .class("title", Style::new().size(22.0).bold())
+        .class(
+            "accent",
+            Style::new()
+                .color(Color::rgb(0x4080F0))
+                .border(Border::new(2, Color::rgb(0x4080F0))),
+        )
+}
+
+fn light() -> Theme {
+    Theme::new()
+        .root(
+            Style::new()
+                .background(Color::rgb(0xF0F0F0))
+                .color(Color::rgb(0x202020))
+                .size(16.0),
+        )
+        .class(
+            "card",
+            Style::new()
+                .background(Color::WHITE)
+                .corner_radius(4)
+                .padding(10)
+                .border(Border::new(1, Color::rgb(0xC0C0C0))),
+        )
+        .class("title", Style::new().size(22.0).italic(true))
+        .class("accent", Style::new().color(Color::rgb(0xC03030)))
+}
+
+/// Two cards styled only through classes; the second also has "accent".
+/// Returns the title and body of each card.
+fn screen() -> (Screen<HeadlessBackend>, Vec<[Arc<Widget>; 2]>) {
+    let mut screen = Screen::headless(240, 120);
+    let mut texts = Vec::new();
+    for (i, classes) in ["card", "card accent"].into_iter().enumerate() {
+        let mut card = Div::default();
+        let title = card
+            .draw(Text::new("Title"))
+            .component(Class("title"))
+            .clone();
+        let body = card
+            .draw(Text::new("Body text"))
+            .component(Transform::new().top(30))
+            .clone();
+        texts.push([title, body]);
+        screen
+            .draw(card)
+            .component(at(12 + i as i32 * 116, 16, 100, 88))
+            .component(Class(classes));
+    }
+    (screen, texts)
+}
+
+/// Whether any pixel of `widget` in the last frame is exactly `color`.
+fn draws(screen: &Screen<HeadlessBackend>, widget: &Arc<Widget>, color: u32) -> bool {
+    let (x, y, width, height) = bounds(widget);
+    let (x, y) = (x as usize, y as usize);
+    (y..y + height).any(|y| (x..x + width).any(|x| pixel(screen, x, y) == color))
+}
+
+#[test]
+fn themes() {
+    let (mut screen, texts) = screen();
+    screen.set_theme(dark());
+    check("theme_dark", &mut screen);
+
+    assert_eq!(pixel(&screen, 2, 2), 0x101010);
+    assert_eq!(pixel(&screen, 60, 90), 0x2A2A2A);
+    assert_eq!(pixel(&screen, 128 + 1, 60), 0x4080F0);
+    // Text sits inside the card's padding and takes its color and size from
+    // the classes on it and its ancestors.
+    let [title, body] = &texts[0];
+    assert_eq!(bounds(title).0, 12 + 10);
+    assert!(bounds(title).3 > bounds(body).3);
+    assert!(draws(&screen, body, 0xFFFFFF));
+    assert!(draws(&screen, &texts[1][1], 0x4080F0));
+    assert!(!draws(&screen, &texts[1][1], 0xFFFFFF));
+
+    // Switching restyles the same widgets on the next frame.
+    screen.set_theme(light());
+    check("theme_light", &mut screen);
+
+    assert_eq!(pixel(&screen, 2, 2), 0xF0F0F0);
+    assert_eq!(pixel(&screen, 60, 90), 0xFFFFFF);
+    assert_eq!(pixel(&screen, 128, 60), 0xC0C0C0);
+    assert!(draws(&screen, body, 0x202020));
+    assert!(draws(&screen, &texts[1][1], 0xC03030));
+}
+
+#[test]
+fn own_style_wins_over_class() {
+    let mut screen = Screen::headless(120, 60);
+    screen.set_theme(dark());
+    screen
+        .draw(Div {
+            style: DivStyle::new().background(0x40A040),
+            ..Default::default()
+        })
+        .component(at(10, 10, 100, 40))
+        .component(Class("card"));
+    check("theme_override", &mut screen);
+
+    // The div's own background wins; the class still rounds its corners and
+    // casts a shadow, which only darkens the root background there.
+    assert_eq!(pixel(&screen, 60, 30), 0x40A040);
+    let corner = pixel(&screen, 10, 10);
+    assert!(
+        corner <= 0x101010 && corner & 0xFF == corner >> 16,
+        "corner {:06X}",
+        corner
+    );
+}